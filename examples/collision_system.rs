@@ -0,0 +1,34 @@
+//! Runs the simulation of elastic collisions headless, and prints the state of the particles at
+//! regular times: a line with the time, the number of collisions and the kinetic energy, and then
+//! a line with the position and the velocity of each particle.
+//!
+//! ```text
+//! cargo run --example collision_system [particles] [time] [snapshots]
+//! ```
+
+extern crate algorithms;
+extern crate rand;
+
+use std::env;
+use algorithms::collision_system::CollisionSystem;
+use algorithms::event_simulation::Simulation;
+
+fn arg<T: std::str::FromStr>(index: usize, default: T) -> T {
+    env::args().nth(index).and_then(|arg| arg.parse().ok()).unwrap_or(default)
+}
+
+fn main() {
+    let size: usize = arg(1, 20);
+    let time: f64 = arg(2, 10.0);
+    // at least one snapshot after the start, so that the times between them are finite
+    let snapshots: usize = arg(3, 10).max(1);
+
+    let mut simulation = Simulation::new(CollisionSystem::random(size, &mut rand::thread_rng()));
+    for snapshot in 0..=snapshots {
+        simulation.run_until(time * snapshot as f64 / snapshots as f64);
+        let system = simulation.model();
+        println!("t = {} collisions = {} energy = {}", simulation.time(), system.collisions(),
+                 system.kinetic_energy());
+        print!("{}", system);
+    }
+}
@@ -0,0 +1,349 @@
+//! A binary heap, that orders its items by a [`Compare`] function, with the largest item on top.
+//! With the [`Natural`] order, or a compare function, it is a [`MaxPQ`], and with a [`Reversed`]
+//! order, a [`MinPQ`], without wrapping the items.
+//!
+//! It shares its swim and sink operations with the heap of [`max_pq`].
+//!
+//! [`Compare`]: trait.Compare.html
+//! [`Natural`]: struct.Natural.html
+//! [`MaxPQ`]: ../max_pq/trait.MaxPQ.html
+//! [`Reversed`]: struct.Reversed.html
+//! [`MinPQ`]: ../max_pq/trait.MinPQ.html
+//! [`max_pq`]: ../max_pq/index.html
+//!
+//! # Example
+//! ```
+//! use algorithms::binary_heap::{BinaryHeap, MinHeap};
+//!
+//! let mut heap: MinHeap<u32> = vec![5, 1, 8, 3].into_iter().collect();
+//! heap.push(2);
+//! assert_eq!(heap.pop(), Some(1));
+//! assert_eq!(heap.peek(), Some(&2));
+//!
+//! let mut by_length = BinaryHeap::with_compare(|a: &&str, b: &&str| a.len().cmp(&b.len()));
+//! by_length.push("pear");
+//! by_length.push("banana");
+//! by_length.push("fig");
+//! assert_eq!(by_length.into_sorted_vec(), vec!["fig", "pear", "banana"]);
+//! ```
+
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::vec::Drain;
+use ::max_pq::{make_heap_by_less, sink, sort_down_by_less, swim, MaxPQ, MinPQ};
+
+/// an order of items of type T
+pub trait Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// the natural order of [`PartialOrd`] items. Items that are not comparable are ordered as equal.
+///
+/// [`PartialOrd`]: https://doc.rust-lang.org/std/cmp/trait.PartialOrd.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Natural;
+
+impl<T> Compare<T> for Natural
+    where T: PartialOrd {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// the reverse of an order
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Reversed<C>(pub C);
+
+impl<T, C> Compare<T> for Reversed<C>
+    where C: Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0.compare(b, a)
+    }
+}
+
+impl<T, F> Compare<T> for F
+    where F: Fn(&T, &T) -> Ordering {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// An order, in which the item on top of a heap is the maximum: the [`Natural`] order, and compare
+/// functions. A heap in a [`Reversed`] order is a [`MinPQ`] instead, so it does not del_max the
+/// smallest item.
+///
+/// ```compile_fail
+/// use algorithms::binary_heap::MinHeap;
+/// use algorithms::max_pq::MaxPQ;
+///
+/// let mut heap: MinHeap<u32> = MinHeap::new();
+/// heap.del_max();
+/// ```
+///
+/// [`Natural`]: struct.Natural.html
+/// [`Reversed`]: struct.Reversed.html
+/// [`MinPQ`]: ../max_pq/trait.MinPQ.html
+pub trait MaxOrder<T>: Compare<T> {}
+
+impl<T> MaxOrder<T> for Natural
+    where T: PartialOrd {}
+
+impl<T, F> MaxOrder<T> for F
+    where F: Fn(&T, &T) -> Ordering {}
+
+/// a binary heap, with the largest item in the order C on top
+#[derive(Clone, Debug)]
+pub struct BinaryHeap<T, C = Natural> {
+    vec: Vec<T>,
+    compare: C,
+}
+
+/// a binary heap, with the smallest item in the natural order on top
+pub type MinHeap<T> = BinaryHeap<T, Reversed<Natural>>;
+
+impl<T, C> BinaryHeap<T, C>
+    where C: Compare<T> + Default {
+    /// an empty heap, in the default order of C
+    pub fn new() -> BinaryHeap<T, C> {
+        BinaryHeap::with_compare(C::default())
+    }
+}
+
+impl<T, C> Default for BinaryHeap<T, C>
+    where C: Compare<T> + Default {
+    fn default() -> BinaryHeap<T, C> {
+        BinaryHeap::new()
+    }
+}
+
+impl<T, C> BinaryHeap<T, C>
+    where C: Compare<T> {
+    /// an empty heap in the order of compare
+    pub fn with_compare(compare: C) -> BinaryHeap<T, C> {
+        BinaryHeap { vec: Vec::new(), compare }
+    }
+
+    /// a heap of the items of vec, in the order of compare. It takes linear time.
+    pub fn from_vec(mut vec: Vec<T>, compare: C) -> BinaryHeap<T, C> {
+        {
+            let compare = &compare;
+            make_heap_by_less(&mut vec, &mut |a: &T, b: &T| compare.compare(a, b) == Ordering::Less);
+        }
+        BinaryHeap { vec, compare }
+    }
+
+    /// the largest item, if the heap is not empty
+    pub fn peek(&self) -> Option<&T> {
+        self.vec.first()
+    }
+
+    /// inserts the item, and swims it up
+    pub fn push(&mut self, item: T) {
+        self.vec.push(item);
+        let size = self.vec.len();
+        let compare = &self.compare;
+        swim(&mut self.vec, size - 1, &mut |a: &T, b: &T| compare.compare(a, b) == Ordering::Less);
+    }
+
+    /// removes the largest item, if the heap is not empty, and sinks the last item that takes its
+    /// place
+    pub fn pop(&mut self) -> Option<T> {
+        let size = self.vec.len();
+        if size <= 1 {
+            self.vec.pop()
+        } else {
+            self.vec.swap(0, size - 1);
+            let rslt = self.vec.pop();
+            let compare = &self.compare;
+            sink(&mut self.vec, 0, &mut |a: &T, b: &T| compare.compare(a, b) == Ordering::Less);
+            rslt
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// the items in ascending order of C, sorted with heap sort, so a [`MinHeap`] returns the largest
+    /// item first
+    ///
+    /// [`MinHeap`]: type.MinHeap.html
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let BinaryHeap { mut vec, compare } = self;
+        sort_down_by_less(&mut vec, &mut |a: &T, b: &T| compare.compare(a, b) == Ordering::Less);
+        vec
+    }
+
+    /// the items in the order of the heap
+    pub fn into_vec(self) -> Vec<T> {
+        self.vec
+    }
+
+    /// removes all items, in the order of the heap, and leaves the heap empty
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        self.vec.drain(..)
+    }
+}
+
+impl<T, C> FromIterator<T> for BinaryHeap<T, C>
+    where C: Compare<T> + Default {
+    /// a heap of the items, made in linear time
+    fn from_iter<I>(iter: I) -> BinaryHeap<T, C>
+        where I: IntoIterator<Item=T> {
+        BinaryHeap::from_vec(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<T, C> MaxPQ for BinaryHeap<T, C>
+    where C: MaxOrder<T> {
+    type Item = T;
+
+    fn heap_insert(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn del_max(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    fn size(&self) -> usize {
+        self.vec.len()
+    }
+}
+
+impl<T, C> MinPQ for BinaryHeap<T, Reversed<C>>
+    where C: Compare<T> {
+    type Item = T;
+
+    fn heap_insert(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn del_min(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    fn size(&self) -> usize {
+        self.vec.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
+    use std::cmp::Ordering;
+    use ::max_pq::{MaxPQ, MinPQ};
+    use super::{BinaryHeap, Compare, MinHeap, Natural, Reversed};
+
+    fn is_heap<T, C>(heap: &BinaryHeap<T, C>) -> bool
+        where C: Compare<T> {
+        (1..heap.vec.len()).all(|k| heap.compare.compare(&heap.vec[(k - 1) / 2], &heap.vec[k]) != Ordering::Less)
+    }
+
+    #[test]
+    fn compare() {
+        assert_eq!(Natural.compare(&1, &2), Ordering::Less);
+        assert_eq!(Reversed(Natural).compare(&1, &2), Ordering::Greater);
+        assert_eq!(Natural.compare(&f64::NAN, &1.0), Ordering::Equal);
+        assert_eq!((|a: &i32, b: &i32| (a % 3).cmp(&(b % 3))).compare(&5, &3), Ordering::Greater);
+    }
+
+    #[test]
+    fn push_and_pop_like_a_sorted_vec() {
+        let mut rng = thread_rng();
+        let mut heap: BinaryHeap<u32> = BinaryHeap::new();
+        let mut oracle: Vec<u32> = vec![];
+        for _ in 0..1000 {
+            if rng.gen_range(0, 3) == 0 {
+                assert_eq!(heap.pop(), oracle.pop());
+            } else {
+                let item = rng.gen_range(0, 100);
+                heap.push(item);
+                let index = oracle.iter().position(|x| *x > item).unwrap_or(oracle.len());
+                oracle.insert(index, item);
+            }
+            assert!(is_heap(&heap));
+            assert_eq!(heap.peek(), oracle.last());
+            assert_eq!(heap.len(), oracle.len());
+        }
+    }
+
+    #[test]
+    fn min_heap() {
+        let mut heap: MinHeap<i32> = MinHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+        for item in [5, -1, 8, 3, 3] {
+            heap.push(item);
+        }
+        assert_eq!(heap.peek(), Some(&-1));
+        assert_eq!(heap.into_sorted_vec(), vec![8, 5, 3, 3, -1]);
+    }
+
+    #[test]
+    fn custom_compare() {
+        let mut heap = BinaryHeap::with_compare(|a: &(u32, char), b: &(u32, char)| a.1.cmp(&b.1));
+        heap.push((1, 'b'));
+        heap.push((2, 'c'));
+        heap.push((3, 'a'));
+        assert_eq!(heap.pop(), Some((2, 'c')));
+        assert_eq!(heap.pop(), Some((1, 'b')));
+        assert_eq!(heap.pop(), Some((3, 'a')));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn from_iter_and_into_sorted_vec() {
+        let mut rng = thread_rng();
+        for size in (0..20).map(|x| x * x) {
+            let vec: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+            let heap: BinaryHeap<u32> = vec.iter().cloned().collect();
+            assert!(is_heap(&heap));
+
+            let mut sorted = vec.clone();
+            sorted.sort();
+            assert_eq!(heap.into_sorted_vec(), sorted);
+        }
+    }
+
+    #[test]
+    fn drain() {
+        let mut heap: BinaryHeap<u32> = vec![3, 1, 4, 1, 5].into_iter().collect();
+        let mut drained: Vec<u32> = heap.drain().collect();
+        assert!(heap.is_empty());
+        drained.sort();
+        assert_eq!(drained, vec![1, 1, 3, 4, 5]);
+
+        heap.push(2);
+        assert_eq!(heap.into_vec(), vec![2]);
+    }
+
+    #[test]
+    fn priority_queues() {
+        let mut max_pq: BinaryHeap<u32> = BinaryHeap::new();
+        let mut min_pq: MinHeap<u32> = MinHeap::new();
+        for item in [4, 7, 1] {
+            MaxPQ::heap_insert(&mut max_pq, item);
+            MinPQ::heap_insert(&mut min_pq, item);
+        }
+        assert_eq!(MaxPQ::size(&max_pq), 3);
+        assert_eq!(MinPQ::size(&min_pq), 3);
+        assert_eq!(max_pq.del_max(), Some(7));
+        assert_eq!(min_pq.del_min(), Some(1));
+        assert!(!MinPQ::is_empty(&min_pq));
+    }
+}
@@ -0,0 +1,359 @@
+//! The simulation of elastic collisions of the course: hard discs move in straight lines in the
+//! unit square, and bounce off each other and off the walls. It is an [`event_simulation`], whose
+//! events are the collisions. After each collision, the simulation predicts the next collisions
+//! of the discs that were involved, with the walls and with each other disc, which takes linear
+//! time.
+//!
+//! The system runs headless: it implements [`Display`], with one line of the position and the
+//! velocity of each particle, for printing its state, as the `collision_system` example does.
+//!
+//! [`event_simulation`]: ../event_simulation/index.html
+//! [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+//!
+//! # Example
+//! ```
+//! use algorithms::collision_system::{CollisionSystem, Particle};
+//! use algorithms::event_simulation::Simulation;
+//!
+//! // two discs, that collide head on at time 0.2, and exchange their velocities, up to rounding
+//! let mut simulation = Simulation::new(CollisionSystem::new(vec![
+//!     Particle::new((0.25, 0.5), (1.0, 0.0), 0.05, 1.0),
+//!     Particle::new((0.75, 0.5), (-1.0, 0.0), 0.05, 1.0),
+//! ]));
+//! simulation.run_until(0.3);
+//!
+//! let system = simulation.model();
+//! assert_eq!(system.collisions(), 1);
+//! let (vx, _) = system.particles()[0].velocity();
+//! assert!((vx + 1.0).abs() < 1e-9);
+//! let (vx, _) = system.particles()[1].velocity();
+//! assert!((vx - 1.0).abs() < 1e-9);
+//! println!("{}", system);
+//! ```
+
+extern crate rand;
+
+use self::rand::{ThreadRng, Rng};
+use std::fmt;
+use ::event_simulation::{EventQueue, Model};
+
+/// a hard disc in the unit square
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Particle {
+    rx: f64,
+    ry: f64,
+    vx: f64,
+    vy: f64,
+    radius: f64,
+    mass: f64,
+}
+
+impl Particle {
+    pub fn new(position: (f64, f64), velocity: (f64, f64), radius: f64, mass: f64) -> Particle {
+        let (rx, ry) = position;
+        let (vx, vy) = velocity;
+        Particle { rx, ry, vx, vy, radius, mass }
+    }
+
+    pub fn position(&self) -> (f64, f64) {
+        (self.rx, self.ry)
+    }
+
+    pub fn velocity(&self) -> (f64, f64) {
+        (self.vx, self.vy)
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn mass(&self) -> f64 {
+        self.mass
+    }
+
+    pub fn kinetic_energy(&self) -> f64 {
+        0.5 * self.mass * (self.vx * self.vx + self.vy * self.vy)
+    }
+
+    /// moves the particle in a straight line for the time dt
+    pub fn move_by(&mut self, dt: f64) {
+        self.rx += self.vx * dt;
+        self.ry += self.vy * dt;
+    }
+
+    /// the time until the particle hits the other one, or infinity, if they do not approach each
+    /// other on a course to collide
+    pub fn time_to_hit(&self, that: &Particle) -> f64 {
+        let (dx, dy) = (that.rx - self.rx, that.ry - self.ry);
+        let (dvx, dvy) = (that.vx - self.vx, that.vy - self.vy);
+        let dvdr = dx * dvx + dy * dvy;
+        if dvdr >= 0.0 {
+            return f64::INFINITY;
+        }
+        let dvdv = dvx * dvx + dvy * dvy;
+        let drdr = dx * dx + dy * dy;
+        let sigma = self.radius + that.radius;
+        let d = dvdr * dvdr - dvdv * (drdr - sigma * sigma);
+        if d < 0.0 {
+            return f64::INFINITY;
+        }
+        let dt = -(dvdr + d.sqrt()) / dvdv;
+        if dt > 0.0 { dt } else { f64::INFINITY }
+    }
+
+    /// the time until the particle hits the left or the right wall, or infinity
+    pub fn time_to_hit_vertical_wall(&self) -> f64 {
+        time_to_hit_wall(self.rx, self.vx, self.radius)
+    }
+
+    /// the time until the particle hits the bottom or the top wall, or infinity
+    pub fn time_to_hit_horizontal_wall(&self) -> f64 {
+        time_to_hit_wall(self.ry, self.vy, self.radius)
+    }
+
+    /// changes the velocities of the particles that touch each other, as in an elastic collision
+    pub fn bounce_off(&mut self, that: &mut Particle) {
+        let (dx, dy) = (that.rx - self.rx, that.ry - self.ry);
+        let (dvx, dvy) = (that.vx - self.vx, that.vy - self.vy);
+        let dvdr = dx * dvx + dy * dvy;
+        let dist = self.radius + that.radius;
+
+        // the impulse along the line of the centers
+        let magnitude = 2.0 * self.mass * that.mass * dvdr / ((self.mass + that.mass) * dist);
+        let fx = magnitude * dx / dist;
+        let fy = magnitude * dy / dist;
+
+        self.vx += fx / self.mass;
+        self.vy += fy / self.mass;
+        that.vx -= fx / that.mass;
+        that.vy -= fy / that.mass;
+    }
+
+    pub fn bounce_off_vertical_wall(&mut self) {
+        self.vx = -self.vx;
+    }
+
+    pub fn bounce_off_horizontal_wall(&mut self) {
+        self.vy = -self.vy;
+    }
+}
+
+// the time until a disc at position r, with velocity v, hits one of the walls at 0 and 1. A disc
+// that has overshot a wall by a rounding error hits it at once.
+fn time_to_hit_wall(r: f64, v: f64, radius: f64) -> f64 {
+    if v > 0.0 {
+        ((1.0 - radius - r) / v).max(0.0)
+    } else if v < 0.0 {
+        ((radius - r) / v).max(0.0)
+    } else {
+        f64::INFINITY
+    }
+}
+
+impl fmt::Display for Particle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.rx, self.ry, self.vx, self.vy)
+    }
+}
+
+/// the collisions of the simulation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Collision {
+    Particles(usize, usize),
+    VerticalWall(usize),
+    HorizontalWall(usize),
+}
+
+/// the particles of the simulation, whose events are their collisions
+#[derive(Clone, Debug)]
+pub struct CollisionSystem {
+    particles: Vec<Particle>,
+    collisions: usize,
+}
+
+impl CollisionSystem {
+    pub fn new(particles: Vec<Particle>) -> CollisionSystem {
+        CollisionSystem { particles, collisions: 0 }
+    }
+
+    /// Makes a system of size particles with random velocities, one in each cell of a grid that
+    /// covers the unit square, so that they do not overlap.
+    pub fn random(size: usize, rng: &mut ThreadRng) -> CollisionSystem {
+        let side = (1..).find(|side| side * side >= size).unwrap();
+        let cell = 1.0 / side as f64;
+        let radius = cell / 4.0;
+        let particles = (0..size).map(|i| {
+            let rx = ((i % side) as f64 + 0.5) * cell + rng.gen_range(-radius, radius);
+            let ry = ((i / side) as f64 + 0.5) * cell + rng.gen_range(-radius, radius);
+            let velocity = (rng.gen_range(-0.5, 0.5), rng.gen_range(-0.5, 0.5));
+            Particle::new((rx, ry), velocity, radius, rng.gen_range(0.5, 1.0))
+        }).collect();
+        CollisionSystem::new(particles)
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// the number of collisions so far, with the walls and between particles
+    pub fn collisions(&self) -> usize {
+        self.collisions
+    }
+
+    pub fn kinetic_energy(&self) -> f64 {
+        self.particles.iter().map(Particle::kinetic_energy).sum()
+    }
+
+    // schedules the collisions of particle a with the walls and the other particles
+    fn predict(&self, a: usize, queue: &mut EventQueue<Collision>) {
+        let now = queue.time();
+        let particle = &self.particles[a];
+        for (b, other) in self.particles.iter().enumerate() {
+            let dt = particle.time_to_hit(other);
+            if b != a && dt < f64::INFINITY {
+                queue.schedule(now + dt, &[a, b], Collision::Particles(a, b));
+            }
+        }
+        let dt = particle.time_to_hit_vertical_wall();
+        if dt < f64::INFINITY {
+            queue.schedule(now + dt, &[a], Collision::VerticalWall(a));
+        }
+        let dt = particle.time_to_hit_horizontal_wall();
+        if dt < f64::INFINITY {
+            queue.schedule(now + dt, &[a], Collision::HorizontalWall(a));
+        }
+    }
+}
+
+impl Model for CollisionSystem {
+    type Event = Collision;
+
+    fn start(&mut self, queue: &mut EventQueue<Collision>) {
+        for a in 0..self.particles.len() {
+            self.predict(a, queue);
+        }
+    }
+
+    fn advance(&mut self, dt: f64) {
+        for particle in &mut self.particles {
+            particle.move_by(dt);
+        }
+    }
+
+    fn handle(&mut self, collision: Collision, queue: &mut EventQueue<Collision>) {
+        self.collisions += 1;
+        match collision {
+            Collision::Particles(a, b) => {
+                let (low, high) = self.particles.split_at_mut(a.max(b));
+                low[a.min(b)].bounce_off(&mut high[0]);
+                self.predict(a, queue);
+                self.predict(b, queue);
+            }
+            Collision::VerticalWall(a) => {
+                self.particles[a].bounce_off_vertical_wall();
+                self.predict(a, queue);
+            }
+            Collision::HorizontalWall(a) => {
+                self.particles[a].bounce_off_horizontal_wall();
+                self.predict(a, queue);
+            }
+        }
+    }
+}
+
+impl fmt::Display for CollisionSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for particle in &self.particles {
+            writeln!(f, "{}", particle)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::thread_rng;
+    use ::event_simulation::Simulation;
+    use super::{CollisionSystem, Particle};
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn bounce_off_a_wall() {
+        let mut simulation = Simulation::new(CollisionSystem::new(vec![
+            Particle::new((0.5, 0.5), (1.0, 0.5), 0.1, 1.0),
+        ]));
+        // the right wall at 0.4, and the top wall at 0.8
+        assert_eq!(simulation.run_until(0.5), 1);
+        let particle = simulation.model().particles()[0];
+        assert!(close(particle.position().0, 0.8));
+        assert_eq!(particle.velocity(), (-1.0, 0.5));
+
+        assert_eq!(simulation.run_until(1.0), 1);
+        let particle = simulation.model().particles()[0];
+        assert!(close(particle.position().1, 0.8));
+        assert_eq!(particle.velocity(), (-1.0, -0.5));
+    }
+
+    #[test]
+    fn bounce_off_a_heavier_particle() {
+        // the heavy particle is hit off center, and the momentum is conserved
+        let mut system = CollisionSystem::new(vec![
+            Particle::new((0.2, 0.45), (1.0, 0.0), 0.05, 1.0),
+            Particle::new((0.5, 0.5), (0.0, 0.0), 0.05, 3.0),
+        ]);
+        let energy = system.kinetic_energy();
+        let mut simulation = Simulation::new(system);
+        assert_eq!(simulation.run_until(0.3), 1);
+        system = simulation.into_model();
+
+        let momentum = |i: usize| {
+            let particle = system.particles()[i];
+            let (vx, vy) = particle.velocity();
+            (particle.mass() * vx, particle.mass() * vy)
+        };
+        assert!(close(momentum(0).0 + momentum(1).0, 1.0));
+        assert!(close(momentum(0).1 + momentum(1).1, 0.0));
+        assert!(close(system.kinetic_energy(), energy));
+        assert!(system.particles()[1].velocity().1 > 0.0);
+    }
+
+    #[test]
+    fn random_system_keeps_its_energy() {
+        let mut rng = thread_rng();
+        let system = CollisionSystem::random(50, &mut rng);
+        let energy = system.kinetic_energy();
+        let mut simulation = Simulation::new(system);
+        for step in 1..=20 {
+            simulation.run_until(step as f64 * 0.5);
+            let system = simulation.model();
+            assert!((system.kinetic_energy() - energy).abs() < 1e-9 * energy);
+            for (a, particle) in system.particles().iter().enumerate() {
+                let (rx, ry) = particle.position();
+                let radius = particle.radius();
+                for r in [rx, ry] {
+                    assert!(r >= radius - 1e-9 && r <= 1.0 - radius + 1e-9);
+                }
+                for other in &system.particles()[a + 1..] {
+                    let (ox, oy) = other.position();
+                    let distance = ((ox - rx).powi(2) + (oy - ry).powi(2)).sqrt();
+                    assert!(distance >= radius + other.radius() - 1e-9);
+                }
+            }
+        }
+        assert!(simulation.model().collisions() > 50);
+    }
+
+    #[test]
+    fn display() {
+        let system = CollisionSystem::new(vec![
+            Particle::new((0.5, 0.25), (1.0, 0.0), 0.1, 1.0),
+            Particle::new((0.125, 0.75), (0.0, -0.5), 0.1, 1.0),
+        ]);
+        assert_eq!(format!("{}", system), "0.5 0.25 1 0\n0.125 0.75 0 -0.5\n");
+    }
+}
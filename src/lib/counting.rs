@@ -0,0 +1,314 @@
+//! Instrumentation, that counts the compares, exchanges and array accesses of the sorting algorithms,
+//! as they are analysed in the course.
+//!
+//! Compares are counted by wrapping the items in [`Counted`], exchanges and array accesses by
+//! wrapping the collection in [`Instrumented`]. The counts are kept per thread.
+//!
+//! [`Counted`]: struct.Counted.html
+//! [`Instrumented`]: struct.Instrumented.html
+//!
+//! # Example
+//! ```
+//! use algorithms::counting::{count, Counted, Instrumented};
+//! use algorithms::insertion_sort::insertion_sort;
+//!
+//! let mut vec: Vec<Counted<i32>> = vec![3, 2, 1].into_iter().map(Counted).collect();
+//! let counts = count(|| insertion_sort(&mut Instrumented::new(&mut vec)));
+//!
+//! assert_eq!(counts.compares, 3);
+//! assert_eq!(counts.exchanges, 3);
+//! assert_eq!(counts.array_accesses, 6);
+//! ```
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::ops::{Index, IndexMut};
+use ::index_ops::{Length, Swap};
+
+/// the numbers of operations, counted since the last [`reset`]
+///
+/// [`reset`]: fn.reset.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OperationCounts {
+    /// compares of [`Counted`] items
+    ///
+    /// [`Counted`]: struct.Counted.html
+    pub compares: usize,
+    /// calls to [`Swap::swap`] of an [`Instrumented`] collection
+    ///
+    /// [`Swap::swap`]: ../index_ops/trait.Swap.html#tymethod.swap
+    /// [`Instrumented`]: struct.Instrumented.html
+    pub exchanges: usize,
+    /// calls to `index` and `index_mut` of an [`Instrumented`] collection.
+    /// The accesses of an exchange are not included.
+    ///
+    /// [`Instrumented`]: struct.Instrumented.html
+    pub array_accesses: usize,
+}
+
+thread_local! {
+    static COUNTS: Cell<OperationCounts> = Cell::new(OperationCounts::default());
+}
+
+fn increment<F>(f: F)
+    where F: FnOnce(&mut OperationCounts) {
+    COUNTS.with(|counts| {
+        let mut c = counts.get();
+        f(&mut c);
+        counts.set(c);
+    });
+}
+
+/// sets all counts of the current thread to 0
+pub fn reset() {
+    COUNTS.with(|counts| counts.set(OperationCounts::default()));
+}
+
+/// the counts of the current thread
+pub fn counts() -> OperationCounts {
+    COUNTS.with(|counts| counts.get())
+}
+
+/// resets the counts, runs f, and returns the operations it counted
+pub fn count<F>(f: F) -> OperationCounts
+    where F: FnOnce() {
+    reset();
+    f();
+    counts()
+}
+
+/// an item, that counts how often it is compared
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Counted<T>(pub T);
+
+impl<T> PartialEq for Counted<T>
+    where T: PartialEq {
+    fn eq(&self, other: &Counted<T>) -> bool {
+        increment(|c| c.compares += 1);
+        self.0 == other.0
+    }
+}
+
+impl<T> PartialOrd for Counted<T>
+    where T: PartialOrd {
+    fn partial_cmp(&self, other: &Counted<T>) -> Option<Ordering> {
+        increment(|c| c.compares += 1);
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+/// an adapter for types that implement [`Index`], [`IndexMut`], [`Length`] and [`Swap`],
+/// that counts the array accesses and exchanges.
+///
+/// [`Index`]: https://doc.rust-lang.org/std/ops/trait.Index.html
+/// [`IndexMut`]: https://doc.rust-lang.org/std/ops/trait.IndexMut.html
+/// [`Length`]: ../index_ops/trait.Length.html
+/// [`Swap`]: ../index_ops/trait.Swap.html
+pub struct Instrumented<'a, Underl>
+    where Underl: 'a + ?Sized {
+    underl: &'a mut Underl,
+}
+
+impl<'a, Underl> Instrumented<'a, Underl>
+    where Underl: ?Sized {
+    pub fn new(underl: &'a mut Underl) -> Instrumented<'a, Underl> {
+        Instrumented { underl }
+    }
+}
+
+impl<'a, Underl, Output> Index<usize> for Instrumented<'a, Underl>
+    where Underl: ?Sized + Index<usize, Output=Output> {
+    type Output = Output;
+
+    fn index(&self, index: usize) -> &Output {
+        increment(|c| c.array_accesses += 1);
+        &self.underl[index]
+    }
+}
+
+impl<'a, Underl, Output> IndexMut<usize> for Instrumented<'a, Underl>
+    where Underl: ?Sized + IndexMut<usize, Output=Output> {
+    fn index_mut(&mut self, index: usize) -> &mut Output {
+        increment(|c| c.array_accesses += 1);
+        &mut self.underl[index]
+    }
+}
+
+impl<'a, Underl> Length for Instrumented<'a, Underl>
+    where Underl: ?Sized + Length {
+    fn length(&self) -> usize {
+        self.underl.length()
+    }
+}
+
+impl<'a, Underl, Item> Swap<Item> for Instrumented<'a, Underl>
+    where Underl: ?Sized + Swap<Item> {
+    fn swap(&mut self, a: usize, b: usize) {
+        increment(|c| c.exchanges += 1);
+        self.underl.swap(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
+    use super::{count, Counted, Instrumented, OperationCounts};
+    use ::insertion_sort::insertion_sort;
+    use ::is_sorted::is_sorted;
+    use ::max_pq::heap_sort;
+    use ::merge_sort::{bottom_up_merge_sort, merge_sort, natural_merge_sort};
+    use ::quick_sort::{intro_sort, quick_sort};
+    use ::selection_sort::selection_sort;
+    use ::shell_sort::{shell_sort, shell_sort_with, Pratt};
+
+    const SIZE: usize = 1024;
+
+    fn lg(n: usize) -> f64 {
+        (n as f64).log2()
+    }
+
+    fn ascending(size: usize) -> Vec<Counted<usize>> {
+        (0..size).map(Counted).collect()
+    }
+
+    fn descending(size: usize) -> Vec<Counted<usize>> {
+        (0..size).rev().map(Counted).collect()
+    }
+
+    fn random(size: usize) -> Vec<Counted<u32>> {
+        let mut rng = thread_rng();
+        (0..size).map(|_| Counted(rng.gen())).collect()
+    }
+
+    fn count_sort<Item, F>(vec: &mut Vec<Counted<Item>>, sort: F) -> OperationCounts
+        where Item: PartialOrd,
+              F: FnOnce(&mut Instrumented<Vec<Counted<Item>>>) {
+        let counts = count(|| sort(&mut Instrumented::new(vec)));
+        assert!(is_sorted(vec));
+        counts
+    }
+
+    #[test]
+    fn counted() {
+        let counts = count(|| {
+            assert!(Counted(1) < Counted(2));
+            assert!(Counted(1) != Counted(2));
+            assert!(Counted(2) >= Counted(2));
+        });
+        assert_eq!(counts, OperationCounts { compares: 3, exchanges: 0, array_accesses: 0 });
+    }
+
+    #[test]
+    fn instrumented() {
+        let mut vec = vec![1, 2, 3];
+        let counts = count(|| {
+            let mut instrumented = Instrumented::new(&mut vec);
+            instrumented[0] = instrumented[1] + instrumented[2];
+            ::index_ops::Swap::swap(&mut instrumented, 1, 2);
+        });
+        assert_eq!(counts, OperationCounts { compares: 0, exchanges: 1, array_accesses: 3 });
+        assert_eq!(vec, vec![5, 3, 2]);
+    }
+
+    // selection sort uses ~N^2/2 compares and N - 1 exchanges, for any input
+    #[test]
+    fn selection_sort_counts() {
+        for vec in &mut [random(SIZE).into_iter().map(|c| Counted(c.0 as usize)).collect(), ascending(SIZE), descending(SIZE)] {
+            let counts = count_sort(vec, |vec| selection_sort(vec));
+            assert_eq!(counts.compares, (SIZE - 1) * (SIZE + 2) / 2);
+            assert_eq!(counts.exchanges, SIZE - 1);
+            assert_eq!(counts.array_accesses, 2 * counts.compares);
+        }
+    }
+
+    // insertion sort uses ~N^2/2 compares and exchanges on descending input,
+    // and N - 1 compares and no exchanges on ascending input
+    #[test]
+    fn insertion_sort_counts() {
+        let counts = count_sort(&mut descending(SIZE), |vec| insertion_sort(vec));
+        assert_eq!(counts.compares, SIZE * (SIZE - 1) / 2);
+        assert_eq!(counts.exchanges, SIZE * (SIZE - 1) / 2);
+
+        let counts = count_sort(&mut ascending(SIZE), |vec| insertion_sort(vec));
+        assert_eq!(counts.compares, SIZE - 1);
+        assert_eq!(counts.exchanges, 0);
+
+        // ~N^2/4 on random input
+        let counts = count_sort(&mut random(SIZE), |vec| insertion_sort(vec));
+        assert!(counts.exchanges as f64 > 0.2 * (SIZE * SIZE) as f64);
+        assert!((counts.exchanges as f64) < 0.3 * (SIZE * SIZE) as f64);
+    }
+
+    // merge sort uses at most N lg N compares, and no exchanges. On ascending input, it skips all
+    // the merges, and uses N - 1 compares.
+    #[test]
+    fn merge_sort_counts() {
+        let counts = count_sort(&mut ascending(SIZE), |vec| merge_sort(vec));
+        assert_eq!(counts.compares, SIZE - 1);
+        assert_eq!(counts.exchanges, 0);
+
+        let counts = count_sort(&mut random(SIZE), |vec| merge_sort(vec));
+        assert!(counts.compares as f64 <= SIZE as f64 * lg(SIZE));
+        // copying to and from the auxilary vector: at most 6 N lg N array accesses
+        assert!(counts.array_accesses as f64 <= 6.0 * SIZE as f64 * lg(SIZE));
+    }
+
+    // bottom-up and natural merge sort also use N - 1 compares on ascending input,
+    // and natural merge sort finds a descending input as a single run
+    #[test]
+    fn bottom_up_and_natural_merge_sort_counts() {
+        let counts = count_sort(&mut ascending(SIZE), |vec| bottom_up_merge_sort(vec));
+        assert_eq!(counts.compares, SIZE - 1);
+        let counts = count_sort(&mut random(SIZE), |vec| bottom_up_merge_sort(vec));
+        assert!(counts.compares as f64 <= SIZE as f64 * lg(SIZE));
+
+        let counts = count_sort(&mut ascending(SIZE), |vec| natural_merge_sort(vec));
+        assert_eq!(counts.compares, SIZE - 1);
+        let counts = count_sort(&mut descending(SIZE), |vec| natural_merge_sort(vec));
+        assert_eq!(counts.compares, SIZE - 1);
+        let counts = count_sort(&mut random(SIZE), |vec| natural_merge_sort(vec));
+        assert!(counts.compares as f64 <= SIZE as f64 * lg(SIZE) + SIZE as f64);
+    }
+
+    // quick sort uses ~1.39 N lg N compares on average
+    #[test]
+    fn quick_sort_counts() {
+        let counts = count_sort(&mut random(SIZE), |vec| quick_sort(vec, &mut thread_rng()));
+        assert!(counts.compares as f64 > SIZE as f64 * lg(SIZE));
+        assert!((counts.compares as f64) < 2.0 * SIZE as f64 * lg(SIZE));
+    }
+
+    // intro sort needs no shuffle: it uses less than 2 N lg N compares on random, ascending,
+    // descending and constant input
+    #[test]
+    fn intro_sort_counts() {
+        let constant = || (0..SIZE).map(|_| Counted(0)).collect();
+        for vec in &mut [random(SIZE).into_iter().map(|c| Counted(c.0 as usize)).collect(), ascending(SIZE), descending(SIZE), constant()] {
+            let counts = count_sort(vec, |vec| intro_sort(vec));
+            assert!((counts.compares as f64) < 2.0 * SIZE as f64 * lg(SIZE));
+        }
+    }
+
+    // heap sort with bottom-up sinking uses about N lg N compares, instead of 2 N lg N, and
+    // N lg N exchanges, together with the at most 2 N compares and N exchanges of making the heap
+    #[test]
+    fn heap_sort_counts() {
+        let counts = count_sort(&mut random(SIZE), |vec| heap_sort(vec));
+        assert!(counts.compares as f64 <= SIZE as f64 * lg(SIZE) + 2.0 * SIZE as f64);
+        assert!(counts.exchanges as f64 <= SIZE as f64 * lg(SIZE) + 2.0 * SIZE as f64);
+    }
+
+    // shell sort with 3x+1 increments uses less than N^(3/2) compares and exchanges on random
+    // input, and with Pratt's increments less than N lg^2 N.
+    #[test]
+    fn shell_sort_counts() {
+        let counts = count_sort(&mut random(SIZE), |vec| shell_sort(vec));
+        assert!((counts.compares as f64) < (SIZE as f64).powf(1.5));
+        assert!((counts.exchanges as f64) < (SIZE as f64).powf(1.5));
+
+        let counts = count_sort(&mut random(SIZE), |vec| shell_sort_with(vec, &Pratt));
+        assert!((counts.compares as f64) < SIZE as f64 * lg(SIZE) * lg(SIZE));
+    }
+}
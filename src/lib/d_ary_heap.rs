@@ -0,0 +1,219 @@
+//! A d-ary heap: a heap-ordered complete tree, like the binary heap of [`max_pq`], in which each
+//! node has d children instead of 2.
+//!
+//! The tree is only log<sub>d</sub> N high, so inserting, which swims up, takes fewer compares
+//! than in a binary heap, and so would increasing a key. Deleting the maximum sinks down, which
+//! takes d compares per level. The children of a node are adjacent in the array, so for a small d
+//! like 4, the heap also makes better use of the cache.
+//!
+//! [`max_pq`]: ../max_pq/index.html
+//!
+//! # Example
+//! ```
+//! use algorithms::d_ary_heap::DAryHeap;
+//! use algorithms::max_pq::MaxPQ;
+//!
+//! let mut heap = DAryHeap::new(4);
+//! for item in vec![3, 1, 4, 1, 5, 9, 2, 6] {
+//!     heap.heap_insert(item);
+//! }
+//! assert_eq!(heap.max(), Some(&9));
+//! assert_eq!(heap.del_max(), Some(9));
+//! assert_eq!(heap.del_max(), Some(6));
+//! assert_eq!(heap.size(), 6);
+//! ```
+
+use ::max_pq::MaxPQ;
+
+/// the arity of a [`DAryHeap`] made with `default()`
+///
+/// [`DAryHeap`]: struct.DAryHeap.html
+pub const DEFAULT_ARITY: usize = 4;
+
+/// a heap, in which each node has up to arity children
+#[derive(Clone, Debug)]
+pub struct DAryHeap<T> {
+    vec: Vec<T>,
+    arity: usize,
+}
+
+impl<T> DAryHeap<T>
+    where T: PartialOrd {
+    /// Makes an empty heap, whose nodes have up to arity children.
+    ///
+    /// # Panics
+    /// Panics if the arity is less than 2.
+    pub fn new(arity: usize) -> DAryHeap<T> {
+        DAryHeap::from_vec(Vec::new(), arity)
+    }
+
+    /// Makes a heap of the items of vec, by sinking all nodes with children, in linear time.
+    ///
+    /// # Panics
+    /// Panics if the arity is less than 2.
+    pub fn from_vec(vec: Vec<T>, arity: usize) -> DAryHeap<T> {
+        assert!(arity >= 2, "the arity {} is less than 2", arity);
+        let mut heap = DAryHeap { vec, arity };
+        let size = heap.vec.len();
+        if size > 1 {
+            for k in (0..=heap.parent(size - 1)).rev() {
+                heap.sink(k);
+            }
+        }
+        heap
+    }
+
+    /// the largest item, if the heap is not empty
+    pub fn max(&self) -> Option<&T> {
+        self.vec.first()
+    }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    fn parent(&self, k: usize) -> usize {
+        (k - 1) / self.arity
+    }
+
+    fn first_child(&self, k: usize) -> usize {
+        k * self.arity + 1
+    }
+
+    fn swim(&mut self, k: usize) {
+        let mut i = k;
+        while i > 0 && self.vec[self.parent(i)] < self.vec[i] {
+            let parent = self.parent(i);
+            self.vec.swap(parent, i);
+            i = parent;
+        }
+    }
+
+    fn sink(&mut self, k: usize) {
+        let size = self.vec.len();
+        let mut i = k;
+        loop {
+            let first = self.first_child(i);
+            if first >= size {
+                return;
+            }
+            let last = (first + self.arity).min(size);
+            let mut largest = first;
+            for child in first + 1..last {
+                if self.vec[largest] < self.vec[child] {
+                    largest = child;
+                }
+            }
+            if self.vec[i] < self.vec[largest] {
+                self.vec.swap(i, largest);
+                i = largest;
+            } else {
+                return;
+            }
+        }
+    }
+}
+
+impl<T> Default for DAryHeap<T>
+    where T: PartialOrd {
+    fn default() -> DAryHeap<T> {
+        DAryHeap::new(DEFAULT_ARITY)
+    }
+}
+
+impl<T> MaxPQ for DAryHeap<T>
+    where T: PartialOrd {
+    type Item = T;
+
+    fn heap_insert(&mut self, item: T) {
+        self.vec.push(item);
+        let size = self.vec.len();
+        self.swim(size - 1);
+    }
+
+    fn del_max(&mut self) -> Option<T> {
+        let size = self.vec.len();
+        if size <= 1 {
+            self.vec.pop()
+        } else {
+            self.vec.swap(0, size - 1);
+            let rslt = self.vec.pop();
+            self.sink(0);
+            rslt
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    fn size(&self) -> usize {
+        self.vec.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
+    use ::max_pq::MaxPQ;
+    use super::DAryHeap;
+
+    fn is_d_ary_heap<T>(heap: &DAryHeap<T>) -> bool
+        where T: PartialOrd {
+        (1..heap.vec.len()).all(|k| heap.vec[heap.parent(k)] >= heap.vec[k])
+    }
+
+    #[test]
+    fn insert_and_delete_like_a_sorted_vec() {
+        let mut rng = thread_rng();
+        for arity in 2..6 {
+            let mut heap = DAryHeap::new(arity);
+            let mut oracle: Vec<u32> = vec![];
+            for _ in 0..1000 {
+                if rng.gen_range(0, 3) == 0 {
+                    assert_eq!(heap.del_max(), oracle.pop());
+                } else {
+                    let item = rng.gen_range(0, 100);
+                    heap.heap_insert(item);
+                    let index = oracle.iter().position(|x| *x > item).unwrap_or(oracle.len());
+                    oracle.insert(index, item);
+                }
+                assert!(is_d_ary_heap(&heap));
+                assert_eq!(heap.max(), oracle.last());
+                assert_eq!(heap.size(), oracle.len());
+            }
+        }
+    }
+
+    #[test]
+    fn from_vec() {
+        let mut rng = thread_rng();
+        for size in (0..20).map(|x| x * x) {
+            let vec: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+            let mut heap = DAryHeap::from_vec(vec.clone(), 3);
+            assert!(is_d_ary_heap(&heap));
+
+            let mut sorted = vec;
+            sorted.sort();
+            while let Some(max) = heap.del_max() {
+                assert_eq!(Some(max), sorted.pop());
+            }
+            assert!(sorted.is_empty());
+        }
+    }
+
+    #[test]
+    fn default_arity() {
+        let heap: DAryHeap<u32> = DAryHeap::default();
+        assert_eq!(heap.arity(), 4);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "the arity 1 is less than 2")]
+    fn arity_one() {
+        let _heap: DAryHeap<u32> = DAryHeap::new(1);
+    }
+}
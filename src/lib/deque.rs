@@ -1,4 +1,4 @@
-//! provides an implementation for a double-ended queue (Deque)
+//! provides an implementation for a double-ended queue (Deque), as a doubly linked list
 //! #Example
 //! ```
 //! use algorithms::deque::{Deque, DequeT};
@@ -13,7 +13,48 @@
 //! assert_eq!(deque.size(), 0);
 //! assert_eq!(deque.remove_last(), Option::None);
 //! ```
+//!
+//! The deque can be iterated from both ends, and split and joined like a list:
+//! ```
+//! use algorithms::deque::{Deque, DequeT};
+//!
+//! let mut deque: Deque<i32> = (1..6).collect();
+//! let mut back = deque.split_off(3);
+//! assert_eq!(format!("{:?} {:?}", deque, back), "[1, 2, 3] [4, 5]");
+//!
+//! back.append(&mut deque);
+//! assert!(deque.is_empty());
+//! for item in back.iter_mut() {
+//!     *item *= 10;
+//! }
+//! assert_eq!(back.iter().rev().cloned().collect::<Vec<_>>(), vec![30, 20, 10, 50, 40]);
+//! ```
+//!
+//! A [`CursorMut`] inserts and removes items in the middle of the deque in constant time:
+//! ```
+//! use algorithms::deque::Deque;
+//!
+//! let mut deque: Deque<i32> = (1..6).collect();
+//! let mut cursor = deque.cursor_front_mut();
+//! cursor.move_next();
+//! assert_eq!(cursor.remove_current(), Some(2));
+//! cursor.insert_after(7);
+//! assert_eq!(cursor.current(), Some(&mut 3));
+//! let back = cursor.split_after();
+//! assert_eq!(format!("{:?} {:?}", deque, back), "[1, 3] [7, 4, 5]");
+//! ```
+//!
+//! The pointers between the nodes are not checked by the compiler, so the tests of this module
+//! also run under Miri, which detects uses after free and violations of the aliasing rules:
+//! `cargo +nightly miri test --lib deque`.
+//!
+//! [`CursorMut`]: struct.CursorMut.html
 
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Index, IndexMut};
 use std::option::Option::*;
 use std::ptr::NonNull;
 
@@ -45,9 +86,147 @@ impl<Item> Deque<Item> {
     pub fn iter<'a>(&'a self) -> DequeIter<'a, Item> {
         DequeIter {
             next: self.first,
+            next_back: self.last,
+            size: self.size,
             _deque: &self,
         }
     }
+
+    pub fn iter_mut<'a>(&'a mut self) -> DequeIterMut<'a, Item> {
+        DequeIterMut {
+            next: self.first,
+            next_back: self.last,
+            size: self.size,
+            _deque: PhantomData,
+        }
+    }
+
+    pub fn peek_first(&self) -> Option<&Item> {
+        self.first.map(|nnn| unsafe { &(*nnn.as_ptr()).val })
+    }
+
+    pub fn peek_first_mut(&mut self) -> Option<&mut Item> {
+        self.first.map(|nnn| unsafe { &mut (*nnn.as_ptr()).val })
+    }
+
+    pub fn peek_last(&self) -> Option<&Item> {
+        self.last.map(|nnn| unsafe { &(*nnn.as_ptr()).val })
+    }
+
+    pub fn peek_last_mut(&mut self) -> Option<&mut Item> {
+        self.last.map(|nnn| unsafe { &mut (*nnn.as_ptr()).val })
+    }
+
+    /// the item at the index, counted from the first one, in linear time
+    pub fn get(&self, index: usize) -> Option<&Item> {
+        self.node_at(index).map(|nnn| unsafe { &(*nnn.as_ptr()).val })
+    }
+
+    /// the item at the index, counted from the first one, in linear time
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Item> {
+        self.node_at(index).map(|nnn| unsafe { &mut (*nnn.as_ptr()).val })
+    }
+
+    /// removes all items
+    pub fn clear(&mut self) {
+        while self.remove_first().is_some() {}
+    }
+
+    /// moves all items of other to the end of self, in constant time
+    pub fn append(&mut self, other: &mut Deque<Item>) {
+        if other.size == 0 {
+            return;
+        }
+        if self.size == 0 {
+            mem::swap(self, other);
+            return;
+        }
+        Node::connect(self.last, other.first);
+        self.last = other.last;
+        self.size += other.size;
+        other.first = None;
+        other.last = None;
+        other.size = 0;
+    }
+
+    /// Splits the deque in two at the index. Returns a deque of the items from the index on, and
+    /// leaves the ones before it. Takes linear time, to find the node at the index.
+    ///
+    /// # Panics
+    /// Panics if the index is greater than the size of the deque.
+    pub fn split_off(&mut self, at: usize) -> Deque<Item> {
+        assert!(at <= self.size, "index {} is out of range for a deque of size {}", at, self.size);
+        if at == 0 {
+            return mem::take(self);
+        }
+        let mut other = Deque::new();
+        if at == self.size {
+            return other;
+        }
+
+        let node_ptr = self.node_at(at);
+        let prev = node_ptr.and_then(|nnn| unsafe { nnn.as_ref() }.prev);
+        Node::connect(prev, None);
+        Node::connect(None, node_ptr);
+
+        other.first = node_ptr;
+        other.last = self.last;
+        other.size = self.size - at;
+        self.last = prev;
+        self.size = at;
+        other
+    }
+
+    /// a cursor at the first item, or at the ghost position, if the deque is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, Item> {
+        CursorMut {
+            current: self.first,
+            index: 0,
+            deque: self,
+        }
+    }
+
+    /// a cursor at the last item, or at the ghost position, if the deque is empty
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, Item> {
+        CursorMut {
+            current: self.last,
+            index: self.size.saturating_sub(1),
+            deque: self,
+        }
+    }
+
+    /// keeps only the items for which keep returns true, in their order
+    pub fn retain<F>(&mut self, mut keep: F)
+        where F: FnMut(&Item) -> bool {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(item) = cursor.current() {
+            if keep(item) {
+                cursor.move_next();
+            } else {
+                cursor.remove_current();
+            }
+        }
+    }
+
+    // the node at the index, found from the nearer end of the deque
+    fn node_at(&self, index: usize) -> Option<NonNull<Node<Item>>> {
+        if index >= self.size {
+            return None;
+        }
+        if index < self.size / 2 {
+            let mut node_ptr = self.first;
+            for _ in 0..index {
+                node_ptr = node_ptr.and_then(|nnn| unsafe { nnn.as_ref() }.next);
+            }
+            node_ptr
+        } else {
+            let mut node_ptr = self.last;
+            for _ in index + 1..self.size {
+                node_ptr = node_ptr.and_then(|nnn| unsafe { nnn.as_ref() }.prev);
+            }
+            node_ptr
+        }
+    }
 }
 
 impl<Item> DequeT for Deque<Item> {
@@ -124,7 +303,102 @@ impl<Item> DequeT for Deque<Item> {
 
 impl<Item> Drop for Deque<Item> {
     fn drop(&mut self) {
-        while self.remove_first().is_some() {}
+        self.clear();
+    }
+}
+
+impl<Item> Default for Deque<Item> {
+    fn default() -> Deque<Item> {
+        Deque::new()
+    }
+}
+
+impl<Item> Clone for Deque<Item>
+    where Item: Clone {
+    fn clone(&self) -> Deque<Item> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<Item> fmt::Debug for Deque<Item>
+    where Item: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<Item> PartialEq for Deque<Item>
+    where Item: PartialEq {
+    fn eq(&self, other: &Deque<Item>) -> bool {
+        self.size == other.size && self.iter().eq(other.iter())
+    }
+}
+
+impl<Item> Eq for Deque<Item>
+    where Item: Eq {}
+
+impl<Item> Index<usize> for Deque<Item> {
+    type Output = Item;
+
+    /// the item at the index, in linear time. Panics if the index is out of range.
+    fn index(&self, index: usize) -> &Item {
+        let size = self.size;
+        self.get(index)
+            .unwrap_or_else(|| panic!("index {} is out of range for a deque of size {}", index, size))
+    }
+}
+
+impl<Item> IndexMut<usize> for Deque<Item> {
+    /// the item at the index, in linear time. Panics if the index is out of range.
+    fn index_mut(&mut self, index: usize) -> &mut Item {
+        let size = self.size;
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("index {} is out of range for a deque of size {}", index, size))
+    }
+}
+
+impl<Item> FromIterator<Item> for Deque<Item> {
+    fn from_iter<I>(iter: I) -> Deque<Item>
+        where I: IntoIterator<Item=Item> {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<Item> Extend<Item> for Deque<Item> {
+    fn extend<I>(&mut self, iter: I)
+        where I: IntoIterator<Item=Item> {
+        for item in iter {
+            self.add_last(item);
+        }
+    }
+}
+
+impl<Item> IntoIterator for Deque<Item> {
+    type Item = Item;
+    type IntoIter = DequeIntoIter<Item>;
+
+    fn into_iter(self) -> DequeIntoIter<Item> {
+        DequeIntoIter { deque: self }
+    }
+}
+
+impl<'a, Item> IntoIterator for &'a Deque<Item> {
+    type Item = &'a Item;
+    type IntoIter = DequeIter<'a, Item>;
+
+    fn into_iter(self) -> DequeIter<'a, Item> {
+        self.iter()
+    }
+}
+
+impl<'a, Item> IntoIterator for &'a mut Deque<Item> {
+    type Item = &'a mut Item;
+    type IntoIter = DequeIterMut<'a, Item>;
+
+    fn into_iter(self) -> DequeIterMut<'a, Item> {
+        self.iter_mut()
     }
 }
 
@@ -155,9 +429,13 @@ impl<Item> Node<Item> {
     }
 }
 
+/// iterates over the items of a deque from both ends. size counts the items that are left between
+/// next and next_back, so that the ends stop when they meet.
 pub struct DequeIter<'a, Item>
     where Item: 'a {
     next: Option<NonNull<Node<Item>>>,
+    next_back: Option<NonNull<Node<Item>>>,
+    size: usize,
     _deque: &'a Deque<Item>,
 }
 
@@ -166,12 +444,284 @@ impl<'a, Item> Iterator for DequeIter<'a, Item> {
 
     fn next(&mut self) -> Option<&'a Item> {
         match self.next.clone() {
-            None => None,
-            Some(ref nnn) => {
+            Some(ref nnn) if self.size > 0 => {
                 let node = unsafe { &*nnn.as_ptr() };
                 self.next = node.next;
+                self.size -= 1;
+                Some(&node.val)
+            }
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<'a, Item> DoubleEndedIterator for DequeIter<'a, Item> {
+    fn next_back(&mut self) -> Option<&'a Item> {
+        match self.next_back {
+            Some(nnn) if self.size > 0 => {
+                let node = unsafe { &*nnn.as_ptr() };
+                self.next_back = node.prev;
+                self.size -= 1;
                 Some(&node.val)
             }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, Item> ExactSizeIterator for DequeIter<'a, Item> {}
+
+impl<'a, Item> Clone for DequeIter<'a, Item> {
+    fn clone(&self) -> DequeIter<'a, Item> {
+        DequeIter {
+            next: self.next,
+            next_back: self.next_back,
+            size: self.size,
+            _deque: self._deque,
+        }
+    }
+}
+
+/// iterates over mutable references to the items of a deque from both ends
+pub struct DequeIterMut<'a, Item>
+    where Item: 'a {
+    next: Option<NonNull<Node<Item>>>,
+    next_back: Option<NonNull<Node<Item>>>,
+    size: usize,
+    _deque: PhantomData<&'a mut Deque<Item>>,
+}
+
+impl<'a, Item> Iterator for DequeIterMut<'a, Item> {
+    type Item = &'a mut Item;
+
+    fn next(&mut self) -> Option<&'a mut Item> {
+        match self.next {
+            Some(nnn) if self.size > 0 => {
+                let node = unsafe { &mut *nnn.as_ptr() };
+                self.next = node.next;
+                self.size -= 1;
+                Some(&mut node.val)
+            }
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<'a, Item> DoubleEndedIterator for DequeIterMut<'a, Item> {
+    fn next_back(&mut self) -> Option<&'a mut Item> {
+        match self.next_back {
+            Some(nnn) if self.size > 0 => {
+                let node = unsafe { &mut *nnn.as_ptr() };
+                self.next_back = node.prev;
+                self.size -= 1;
+                Some(&mut node.val)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, Item> ExactSizeIterator for DequeIterMut<'a, Item> {}
+
+/// iterates over the items of a deque, that it owns, from both ends
+pub struct DequeIntoIter<Item> {
+    deque: Deque<Item>,
+}
+
+impl<Item> Iterator for DequeIntoIter<Item> {
+    type Item = Item;
+
+    fn next(&mut self) -> Option<Item> {
+        self.deque.remove_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.size, Some(self.deque.size))
+    }
+}
+
+impl<Item> DoubleEndedIterator for DequeIntoIter<Item> {
+    fn next_back(&mut self) -> Option<Item> {
+        self.deque.remove_last()
+    }
+}
+
+impl<Item> ExactSizeIterator for DequeIntoIter<Item> {}
+
+/// A cursor over a deque, that can move in both directions, and insert, remove and split at its
+/// position in constant time. Besides the items, the cursor can be at a ghost position between the
+/// last and the first item, from where it moves to the first item forward and to the last one
+/// backward.
+pub struct CursorMut<'a, Item>
+    where Item: 'a {
+    // the node at the position, or None at the ghost position
+    current: Option<NonNull<Node<Item>>>,
+    // the index of the current node, if there is one
+    index: usize,
+    deque: &'a mut Deque<Item>,
+}
+
+impl<'a, Item> CursorMut<'a, Item> {
+    /// the index of the position, or None at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// the item at the position, or None at the ghost position
+    pub fn current(&mut self) -> Option<&mut Item> {
+        self.current.map(|nnn| unsafe { &mut (*nnn.as_ptr()).val })
+    }
+
+    /// the item after the position, or the first one at the ghost position
+    pub fn peek_next(&mut self) -> Option<&mut Item> {
+        self.next_node().map(|nnn| unsafe { &mut (*nnn.as_ptr()).val })
+    }
+
+    /// the item before the position, or the last one at the ghost position
+    pub fn peek_prev(&mut self) -> Option<&mut Item> {
+        self.prev_node().map(|nnn| unsafe { &mut (*nnn.as_ptr()).val })
+    }
+
+    /// moves to the next item, from the last one to the ghost position, and from there to the
+    /// first one
+    pub fn move_next(&mut self) {
+        self.index = match self.current {
+            None => 0,
+            Some(_) => self.index + 1,
+        };
+        self.current = self.next_node();
+    }
+
+    /// moves to the previous item, from the first one to the ghost position, and from there to the
+    /// last one
+    pub fn move_prev(&mut self) {
+        self.index = match self.current {
+            None => self.deque.size.saturating_sub(1),
+            Some(_) => self.index.saturating_sub(1),
+        };
+        self.current = self.prev_node();
+    }
+
+    /// inserts the item after the position, or as the first one at the ghost position
+    pub fn insert_after(&mut self, item: Item) {
+        match self.current {
+            None => self.deque.add_first(item),
+            Some(nnn) => {
+                let next = unsafe { nnn.as_ref() }.next;
+                let node_ptr = box_and_get_ptr(Node::new(item));
+                Node::connect(Some(nnn), node_ptr);
+                Node::connect(node_ptr, next);
+                if next.is_none() {
+                    self.deque.last = node_ptr;
+                }
+                self.deque.size += 1;
+            }
+        }
+    }
+
+    /// inserts the item before the position, or as the last one at the ghost position
+    pub fn insert_before(&mut self, item: Item) {
+        match self.current {
+            None => self.deque.add_last(item),
+            Some(nnn) => {
+                let prev = unsafe { nnn.as_ref() }.prev;
+                let node_ptr = box_and_get_ptr(Node::new(item));
+                Node::connect(prev, node_ptr);
+                Node::connect(node_ptr, Some(nnn));
+                if prev.is_none() {
+                    self.deque.first = node_ptr;
+                }
+                self.deque.size += 1;
+                self.index += 1;
+            }
+        }
+    }
+
+    /// Removes the item at the position, and moves to the next one. Returns None at the ghost
+    /// position.
+    pub fn remove_current(&mut self) -> Option<Item> {
+        self.current.map(|nnn| {
+            let (prev, next) = {
+                let node = unsafe { nnn.as_ref() };
+                (node.prev, node.next)
+            };
+            Node::connect(prev, next);
+            if prev.is_none() {
+                self.deque.first = next;
+            }
+            if next.is_none() {
+                self.deque.last = prev;
+            }
+            self.deque.size -= 1;
+            self.current = next;
+            deallocate_and_return_item(nnn)
+        })
+    }
+
+    /// Splits the deque after the position, and returns the items after it. At the ghost position,
+    /// that is all items.
+    pub fn split_after(&mut self) -> Deque<Item> {
+        match self.current {
+            None => mem::take(self.deque),
+            Some(nnn) => {
+                let mut other = Deque::new();
+                let next = unsafe { nnn.as_ref() }.next;
+                if next.is_some() {
+                    Node::connect(Some(nnn), None);
+                    Node::connect(None, next);
+                    other.first = next;
+                    other.last = self.deque.last;
+                    other.size = self.deque.size - self.index - 1;
+                    self.deque.last = Some(nnn);
+                    self.deque.size = self.index + 1;
+                }
+                other
+            }
+        }
+    }
+
+    /// Splits the deque before the position, and returns the items before it. At the ghost
+    /// position, that is all items.
+    pub fn split_before(&mut self) -> Deque<Item> {
+        match self.current {
+            None => mem::take(self.deque),
+            Some(nnn) => {
+                let mut other = Deque::new();
+                let prev = unsafe { nnn.as_ref() }.prev;
+                if prev.is_some() {
+                    Node::connect(prev, None);
+                    Node::connect(None, Some(nnn));
+                    other.first = self.deque.first;
+                    other.last = prev;
+                    other.size = self.index;
+                    self.deque.first = Some(nnn);
+                    self.deque.size -= self.index;
+                    self.index = 0;
+                }
+                other
+            }
+        }
+    }
+
+    fn next_node(&self) -> Option<NonNull<Node<Item>>> {
+        match self.current {
+            None => self.deque.first,
+            Some(nnn) => unsafe { nnn.as_ref() }.next,
+        }
+    }
+
+    fn prev_node(&self) -> Option<NonNull<Node<Item>>> {
+        match self.current {
+            None => self.deque.last,
+            Some(nnn) => unsafe { nnn.as_ref() }.prev,
         }
     }
 }
@@ -191,13 +741,15 @@ fn deallocate_and_return_item<Item>(nnn: NonNull<Node<Item>>) -> Item {
 
 #[cfg(test)]
 mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
     use std::fmt::Write;
     use std::mem;
     use std::ptr::NonNull;
     use std::option::Option::*;
     use super::{Deque, DequeT, Node};
 
-
     fn non_null_from_box(bx: Box<i32>) -> Option<NonNull<i32>> {
         let raw = Box::into_raw(bx);
         NonNull::new(raw)
@@ -343,4 +895,337 @@ mod tests {
 
         assert_eq!(output, "3,1,2,")
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn peek() {
+        let mut deque = Deque::<i32>::new();
+        assert_eq!(deque.peek_first(), None);
+        assert_eq!(deque.peek_last_mut(), None);
+
+        deque.add_last(1);
+        deque.add_last(2);
+        *deque.peek_first_mut().unwrap() += 10;
+        *deque.peek_last_mut().unwrap() += 20;
+
+        assert_eq!(deque.peek_first(), Some(&11));
+        assert_eq!(deque.peek_last(), Some(&22));
+    }
+
+    #[test]
+    fn get_and_index() {
+        let mut deque: Deque<i32> = (0..9).collect();
+        for i in 0..9 {
+            assert_eq!(deque.get(i), Some(&(i as i32)));
+            deque[i] *= 2;
+        }
+        assert_eq!(deque.get(9), None);
+        assert_eq!(deque[8], 16);
+        *deque.get_mut(0).unwrap() = -1;
+        assert_eq!(deque.peek_first(), Some(&-1));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of range for a deque of size 3")]
+    fn index_out_of_range() {
+        let deque: Deque<i32> = (0..3).collect();
+        let _item = deque[3];
+    }
+
+    #[test]
+    fn iter_from_both_ends() {
+        let deque: Deque<i32> = (1..6).collect();
+        let mut iter = deque.iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.clone().next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(deque.iter().rev().cloned().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+        assert_eq!((&deque).into_iter().count(), 5);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut deque: Deque<i32> = (1..6).collect();
+        {
+            let mut iter = deque.iter_mut();
+            *iter.next().unwrap() = 10;
+            *iter.next_back().unwrap() = 50;
+            assert_eq!(iter.len(), 3);
+        }
+        for item in &mut deque {
+            *item += 1;
+        }
+        assert_eq!(deque.iter().cloned().collect::<Vec<_>>(), vec![11, 3, 4, 5, 51]);
+    }
+
+    #[test]
+    fn into_iter() {
+        let deque: Deque<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next_back(), Some("d".to_string()));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn extend_clone_and_compare() {
+        let mut deque: Deque<i32> = Deque::new();
+        deque.extend(vec![1, 2, 3]);
+        let copy = deque.clone();
+        assert_eq!(deque, copy);
+        assert_eq!(format!("{:?}", copy), "[1, 2, 3]");
+
+        deque.add_last(4);
+        assert_ne!(deque, copy);
+        deque.remove_last();
+        deque.add_first(0);
+        deque.remove_last();
+        assert_ne!(deque, copy);
+
+        deque.clear();
+        assert!(deque.is_empty());
+        assert_eq!(deque, Deque::default());
+        assert_eq!(format!("{:?}", deque), "[]");
+    }
+
+    #[test]
+    fn append() {
+        for (a, b) in [(0, 0), (0, 3), (3, 0), (2, 3)] {
+            let mut deque: Deque<i32> = (0..a).collect();
+            let mut other: Deque<i32> = (a..a + b).collect();
+            deque.append(&mut other);
+
+            assert!(other.is_empty());
+            assert_eq!(other.peek_first(), None);
+            assert_eq!(deque.size(), (a + b) as usize);
+            assert_eq!(deque.iter().cloned().collect::<Vec<_>>(), (0..a + b).collect::<Vec<_>>());
+            assert_eq!(deque.iter().rev().cloned().collect::<Vec<_>>(), (0..a + b).rev().collect::<Vec<_>>());
+
+            other.add_last(-1);
+            assert_eq!(other.remove_first(), Some(-1));
+        }
+    }
+
+    #[test]
+    fn split_off() {
+        for size in 0..6 {
+            for at in 0..=size {
+                let mut deque: Deque<i32> = (0..size).collect();
+                let back = deque.split_off(at as usize);
+
+                assert_eq!(deque.size(), at as usize);
+                assert_eq!(back.size(), (size - at) as usize);
+                assert_eq!(deque.iter().cloned().collect::<Vec<_>>(), (0..at).collect::<Vec<_>>());
+                assert_eq!(back.iter().cloned().collect::<Vec<_>>(), (at..size).collect::<Vec<_>>());
+                assert_eq!(deque.iter().rev().cloned().collect::<Vec<_>>(), (0..at).rev().collect::<Vec<_>>());
+                assert_eq!(back.iter().rev().cloned().collect::<Vec<_>>(), (at..size).rev().collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "index 4 is out of range for a deque of size 3")]
+    fn split_off_out_of_range() {
+        let mut deque: Deque<i32> = (0..3).collect();
+        deque.split_off(4);
+    }
+
+    /// the items from first to last, and from last to first, which must be the same
+    fn items(deque: &Deque<i32>) -> Vec<i32> {
+        let items: Vec<i32> = deque.iter().cloned().collect();
+        let mut reversed: Vec<i32> = deque.iter().rev().cloned().collect();
+        reversed.reverse();
+        assert_eq!(items, reversed);
+        assert_eq!(items.len(), deque.size());
+        items
+    }
+
+    #[test]
+    fn cursor_moves_through_the_ghost_position() {
+        let mut deque: Deque<i32> = (0..3).collect();
+        let mut cursor = deque.cursor_front_mut();
+        let mut forward = vec![];
+        for _ in 0..5 {
+            forward.push((cursor.index(), cursor.current().cloned()));
+            cursor.move_next();
+        }
+        assert_eq!(forward, vec![(Some(0), Some(0)), (Some(1), Some(1)), (Some(2), Some(2)),
+                                 (None, None), (Some(0), Some(0))]);
+
+        let mut cursor = deque.cursor_back_mut();
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        let mut backward = vec![];
+        for _ in 0..5 {
+            backward.push((cursor.index(), cursor.current().cloned()));
+            cursor.move_prev();
+        }
+        assert_eq!(backward, vec![(Some(2), Some(2)), (Some(1), Some(1)), (Some(0), Some(0)),
+                                  (None, None), (Some(2), Some(2))]);
+
+        let mut empty = Deque::<i32>::new();
+        let mut cursor = empty.cursor_back_mut();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.remove_current(), None);
+    }
+
+    #[test]
+    fn cursor_inserts() {
+        let mut deque = Deque::<i32>::new();
+        {
+            let mut cursor = deque.cursor_front_mut();
+            cursor.insert_after(2);
+            cursor.insert_before(4);
+            cursor.move_next();
+            assert_eq!(cursor.current(), Some(&mut 2));
+            cursor.insert_before(1);
+            assert_eq!(cursor.index(), Some(1));
+            cursor.insert_after(3);
+            cursor.move_prev();
+            cursor.move_prev();
+            assert_eq!(cursor.index(), None);
+            cursor.insert_after(0);
+            cursor.insert_before(5);
+            *cursor.peek_next().unwrap() -= 10;
+        }
+        assert_eq!(items(&deque), vec![-10, 1, 2, 3, 4, 5]);
+        assert_eq!(deque.peek_first(), Some(&-10));
+        assert_eq!(deque.peek_last(), Some(&5));
+    }
+
+    #[test]
+    fn cursor_removes() {
+        let mut deque: Deque<i32> = (0..5).collect();
+        {
+            let mut cursor = deque.cursor_back_mut();
+            assert_eq!(cursor.remove_current(), Some(4));
+            assert_eq!(cursor.index(), None);
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(0));
+            assert_eq!(cursor.index(), Some(0));
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(2));
+            assert_eq!(cursor.current(), Some(&mut 3));
+        }
+        assert_eq!(items(&deque), vec![1, 3]);
+        assert_eq!(deque.peek_first(), Some(&1));
+        assert_eq!(deque.peek_last(), Some(&3));
+    }
+
+    #[test]
+    fn cursor_splits() {
+        for size in 0..5 {
+            for at in 0..=size {
+                // at the index at, or at the ghost position when at is the size
+                let mut deque: Deque<i32> = (0..size).collect();
+                let (before, after) = {
+                    let mut cursor = deque.cursor_front_mut();
+                    for _ in 0..at {
+                        cursor.move_next();
+                    }
+                    let before = cursor.split_before();
+                    assert_eq!(cursor.index(), if at < size { Some(0) } else { None });
+                    let after = cursor.split_after();
+                    (before, after)
+                };
+                if at < size {
+                    assert_eq!(items(&before), (0..at).collect::<Vec<_>>());
+                    assert_eq!(items(&deque), vec![at]);
+                    assert_eq!(items(&after), (at + 1..size).collect::<Vec<_>>());
+                } else {
+                    assert_eq!(items(&before), (0..size).collect::<Vec<_>>());
+                    assert!(deque.is_empty() && after.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cursor_like_a_vec() {
+        // the position of the cursor in the vec, or None at the ghost position
+        let mut rng = thread_rng();
+        let mut deque = Deque::<i32>::new();
+        let mut oracle: Vec<i32> = vec![];
+        let mut position: Option<usize> = None;
+        for item in 0..300 {
+            {
+                let mut cursor = deque.cursor_front_mut();
+                match position {
+                    None => cursor.move_prev(),
+                    Some(index) => for _ in 0..index {
+                        cursor.move_next();
+                    },
+                }
+                match rng.gen_range(0, 6) {
+                    0 => {
+                        cursor.move_next();
+                        position = match position {
+                            None if oracle.is_empty() => None,
+                            None => Some(0),
+                            Some(index) if index + 1 == oracle.len() => None,
+                            Some(index) => Some(index + 1),
+                        };
+                    }
+                    1 => {
+                        cursor.insert_before(item);
+                        match position {
+                            None => oracle.push(item),
+                            Some(index) => {
+                                oracle.insert(index, item);
+                                position = Some(index + 1);
+                            }
+                        }
+                    }
+                    2 => {
+                        cursor.insert_after(item);
+                        oracle.insert(position.map_or(0, |index| index + 1), item);
+                    }
+                    3 => {
+                        let removed = position.map(|index| oracle.remove(index));
+                        assert_eq!(cursor.remove_current(), removed);
+                        position = position.filter(|index| *index < oracle.len());
+                    }
+                    4 if rng.gen_range(0, 10) == 0 => {
+                        let after = cursor.split_after();
+                        let from = position.map_or(0, |index| index + 1);
+                        assert_eq!(items(&after), oracle.split_off(from));
+                    }
+                    _ => {
+                        cursor.move_prev();
+                        position = match position {
+                            None if oracle.is_empty() => None,
+                            None => Some(oracle.len() - 1),
+                            Some(0) => None,
+                            Some(index) => Some(index - 1),
+                        };
+                    }
+                }
+                assert_eq!(cursor.index(), position);
+                assert_eq!(cursor.current().cloned(), position.map(|index| oracle[index]));
+            }
+            assert_eq!(items(&deque), oracle);
+        }
+    }
+
+    #[test]
+    fn retain() {
+        let mut deque: Deque<i32> = (0..10).collect();
+        deque.retain(|item| item % 3 != 0);
+        assert_eq!(items(&deque), vec![1, 2, 4, 5, 7, 8]);
+        deque.retain(|item| *item > 4);
+        assert_eq!(items(&deque), vec![5, 7, 8]);
+        deque.retain(|_| false);
+        assert!(deque.is_empty());
+        assert_eq!(deque.peek_last(), None);
+    }
+}
@@ -0,0 +1,351 @@
+//! A discrete-event simulation: a [`Model`] changes its state in between events continuously, in a
+//! way that it can predict, and at the events discretely. The simulation keeps the predicted
+//! events in an [`EventQueue`], a [`MinHeap`] ordered by time, and handles them one after the
+//! other, advancing the model to the time of each.
+//!
+//! An event involves some entities of the model, like the particles of a collision. Handling an
+//! event changes its entities, so the other events that were predicted for them may not happen any
+//! more. Rather than removing those from the heap, the queue counts the events of each entity, and
+//! each event remembers the counts of its entities when it was scheduled. An event whose counts
+//! are out of date is invalid, and is skipped when it reaches the top of the heap.
+//!
+//! See [`collision_system`] for the simulation of elastic collisions of the course.
+//!
+//! [`Model`]: trait.Model.html
+//! [`EventQueue`]: struct.EventQueue.html
+//! [`MinHeap`]: ../binary_heap/type.MinHeap.html
+//! [`collision_system`]: ../collision_system/index.html
+//!
+//! # Example
+//! A clock, that ticks once a second, and an alarm, that is reset by a snooze:
+//! ```
+//! use algorithms::event_simulation::{EventQueue, Model, Simulation};
+//!
+//! const ALARM: usize = 0;
+//!
+//! enum Event { Tick, Ring, Snooze }
+//!
+//! struct Clock { ticks: u32, rings: Vec<f64> }
+//!
+//! impl Model for Clock {
+//!     type Event = Event;
+//!
+//!     fn start(&mut self, queue: &mut EventQueue<Event>) {
+//!         queue.schedule(1.0, &[], Event::Tick);
+//!         queue.schedule(5.0, &[ALARM], Event::Ring);
+//!         queue.schedule(3.0, &[ALARM], Event::Snooze);
+//!     }
+//!
+//!     fn advance(&mut self, _dt: f64) {}
+//!
+//!     fn handle(&mut self, event: Event, queue: &mut EventQueue<Event>) {
+//!         let now = queue.time();
+//!         match event {
+//!             Event::Tick => {
+//!                 self.ticks += 1;
+//!                 queue.schedule(now + 1.0, &[], Event::Tick);
+//!             }
+//!             Event::Ring => self.rings.push(now),
+//!             // the ring at 5 is invalid now, as the snooze is an event of the alarm too
+//!             Event::Snooze => queue.schedule(now + 4.0, &[ALARM], Event::Ring),
+//!         }
+//!     }
+//! }
+//!
+//! let mut simulation = Simulation::new(Clock { ticks: 0, rings: vec![] });
+//! simulation.run_until(10.0);
+//! assert_eq!(simulation.model().ticks, 10);
+//! assert_eq!(simulation.model().rings, vec![7.0]);
+//! ```
+
+use std::cmp::Ordering;
+use ::binary_heap::MinHeap;
+
+/// a model of a discrete-event simulation
+pub trait Model {
+    type Event;
+
+    /// schedules the first events
+    fn start(&mut self, queue: &mut EventQueue<Self::Event>);
+
+    /// changes the state as time passes by dt, until the next event
+    fn advance(&mut self, dt: f64);
+
+    /// handles an event at the time of the queue, and schedules the events it predicts
+    fn handle(&mut self, event: Self::Event, queue: &mut EventQueue<Self::Event>);
+}
+
+// an event in the heap, with the counts of its entities when it was scheduled
+struct Scheduled<E> {
+    time: f64,
+    // the order in which the events were scheduled, which breaks ties of the time
+    order: u64,
+    counts: Vec<(usize, usize)>,
+    event: E,
+}
+
+impl<E> PartialEq for Scheduled<E> {
+    fn eq(&self, other: &Scheduled<E>) -> bool {
+        self.time == other.time && self.order == other.order
+    }
+}
+
+impl<E> PartialOrd for Scheduled<E> {
+    fn partial_cmp(&self, other: &Scheduled<E>) -> Option<Ordering> {
+        match self.time.partial_cmp(&other.time) {
+            Some(Ordering::Equal) => Some(self.order.cmp(&other.order)),
+            ordering => ordering,
+        }
+    }
+}
+
+/// the events of a simulation, ordered by time, and the count of events of each entity
+pub struct EventQueue<E> {
+    heap: MinHeap<Scheduled<E>>,
+    time: f64,
+    scheduled: u64,
+    counts: Vec<usize>,
+}
+
+impl<E> EventQueue<E> {
+    pub fn new() -> EventQueue<E> {
+        EventQueue { heap: MinHeap::new(), time: 0.0, scheduled: 0, counts: Vec::new() }
+    }
+
+    /// the current time: of the last event taken from the queue, or that the simulation ran until
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// the number of events of the entity, that were taken from the queue or invalidated
+    pub fn count(&self, entity: usize) -> usize {
+        self.counts.get(entity).cloned().unwrap_or(0)
+    }
+
+    /// the number of events in the queue, including the ones that are invalid but have not been
+    /// skipped yet
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Schedules the event at the time, for the entities. Events at the same time are taken in the
+    /// order in which they were scheduled.
+    ///
+    /// # Panics
+    /// Panics if the time is before the time of the queue, or not a number.
+    pub fn schedule(&mut self, time: f64, entities: &[usize], event: E) {
+        assert!(time >= self.time, "the time {} is before the current time {}", time, self.time);
+        let counts = entities.iter().map(|entity| (*entity, self.count(*entity))).collect();
+        self.heap.push(Scheduled { time, order: self.scheduled, counts, event });
+        self.scheduled += 1;
+    }
+
+    /// invalidates all events of the entity in the queue
+    pub fn invalidate(&mut self, entity: usize) {
+        if entity >= self.counts.len() {
+            self.counts.resize(entity + 1, 0);
+        }
+        self.counts[entity] += 1;
+    }
+
+    /// the time of the next valid event, if there is one. Skips the invalid events before it.
+    pub fn peek_time(&mut self) -> Option<f64> {
+        while let Some(scheduled) = self.heap.peek() {
+            if self.is_valid(scheduled) {
+                return Some(scheduled.time);
+            }
+            self.heap.pop();
+        }
+        None
+    }
+
+    /// Takes the next valid event, if there is one, and moves the time of the queue to it. That
+    /// invalidates the other events of its entities.
+    pub fn pop(&mut self) -> Option<(f64, E)> {
+        self.peek_time()?;
+        let Scheduled { time, counts, event, .. } = self.heap.pop().unwrap();
+        for (entity, _) in counts {
+            self.invalidate(entity);
+        }
+        self.time = time;
+        Some((time, event))
+    }
+
+    fn is_valid(&self, scheduled: &Scheduled<E>) -> bool {
+        scheduled.counts.iter().all(|&(entity, count)| self.count(entity) == count)
+    }
+}
+
+impl<E> Default for EventQueue<E> {
+    fn default() -> EventQueue<E> {
+        EventQueue::new()
+    }
+}
+
+/// a model, and the queue of its events
+pub struct Simulation<M>
+    where M: Model {
+    model: M,
+    queue: EventQueue<M::Event>,
+}
+
+impl<M> Simulation<M>
+    where M: Model {
+    /// starts the simulation of the model at time 0
+    pub fn new(mut model: M) -> Simulation<M> {
+        let mut queue = EventQueue::new();
+        model.start(&mut queue);
+        Simulation { model, queue }
+    }
+
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    pub fn into_model(self) -> M {
+        self.model
+    }
+
+    pub fn queue(&self) -> &EventQueue<M::Event> {
+        &self.queue
+    }
+
+    /// the time the model has been advanced to
+    pub fn time(&self) -> f64 {
+        self.queue.time
+    }
+
+    /// Advances the model to the next valid event and handles it. Returns false if there is none.
+    pub fn step(&mut self) -> bool {
+        let now = self.time();
+        match self.queue.pop() {
+            None => false,
+            Some((time, event)) => {
+                self.model.advance(time - now);
+                self.model.handle(event, &mut self.queue);
+                true
+            }
+        }
+    }
+
+    /// Handles the events until the time, and advances the model to it. Returns the number of
+    /// events handled.
+    ///
+    /// # Panics
+    /// Panics if the time is before the time of the simulation.
+    pub fn run_until(&mut self, time: f64) -> usize {
+        let now = self.time();
+        assert!(time >= now, "the time {} is before the current time {}", time, now);
+        let mut handled = 0;
+        while self.queue.peek_time().is_some_and(|next| next <= time) {
+            self.step();
+            handled += 1;
+        }
+        let now = self.time();
+        self.model.advance(time - now);
+        self.queue.time = time;
+        handled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventQueue, Model, Simulation};
+
+    #[test]
+    fn events_in_order_of_time() {
+        let mut queue = EventQueue::new();
+        for (time, event) in [(2.0, 'c'), (1.0, 'a'), (2.0, 'd'), (1.5, 'b')] {
+            queue.schedule(time, &[], event);
+        }
+        let mut events = vec![];
+        while let Some(event) = queue.pop() {
+            events.push(event);
+        }
+        assert_eq!(events, vec![(1.0, 'a'), (1.5, 'b'), (2.0, 'c'), (2.0, 'd')]);
+        assert_eq!(queue.time(), 2.0);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn invalidated_events_are_skipped() {
+        let mut queue = EventQueue::new();
+        queue.schedule(1.0, &[0, 1], "0 and 1");
+        queue.schedule(2.0, &[1], "1");
+        queue.schedule(3.0, &[2], "2");
+        queue.schedule(4.0, &[], "none");
+
+        queue.invalidate(2);
+        assert_eq!(queue.pop(), Some((1.0, "0 and 1")));
+        // taking the event of 0 and 1 invalidated the other event of 1
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek_time(), Some(4.0));
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.count(0), 1);
+        assert_eq!(queue.count(1), 1);
+        assert_eq!(queue.count(2), 1);
+        assert_eq!(queue.count(3), 0);
+
+        queue.schedule(5.0, &[2], "2 again");
+        assert_eq!(queue.pop(), Some((4.0, "none")));
+        assert_eq!(queue.pop(), Some((5.0, "2 again")));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "the time 0.5 is before the current time 1")]
+    fn schedule_in_the_past() {
+        let mut queue = EventQueue::new();
+        queue.schedule(1.0, &[], ());
+        queue.pop();
+        queue.schedule(0.5, &[], ());
+    }
+
+    /// a point, that moves back and forth between 0 and 1 with speed 1
+    struct Bouncing {
+        position: f64,
+        velocity: f64,
+        bounces: Vec<f64>,
+    }
+
+    impl Model for Bouncing {
+        type Event = ();
+
+        fn start(&mut self, queue: &mut EventQueue<()>) {
+            queue.schedule(1.0 - self.position, &[0], ());
+        }
+
+        fn advance(&mut self, dt: f64) {
+            self.position += self.velocity * dt;
+        }
+
+        fn handle(&mut self, _event: (), queue: &mut EventQueue<()>) {
+            self.velocity = -self.velocity;
+            self.bounces.push(queue.time());
+            let time = queue.time();
+            queue.schedule(time + 1.0, &[0], ());
+        }
+    }
+
+    #[test]
+    fn run_until() {
+        let mut simulation = Simulation::new(Bouncing { position: 0.25, velocity: 1.0, bounces: vec![] });
+        assert_eq!(simulation.run_until(1.0), 1);
+        assert_eq!(simulation.time(), 1.0);
+        assert_eq!(simulation.model().position, 0.75);
+
+        assert_eq!(simulation.run_until(2.75), 2);
+        assert_eq!(simulation.model().position, 1.0);
+        assert_eq!(simulation.model().velocity, -1.0);
+
+        assert!(simulation.step());
+        assert_eq!(simulation.time(), 3.75);
+        assert_eq!(simulation.model().position, 0.0);
+        assert_eq!(simulation.queue().len(), 1);
+        assert_eq!(simulation.into_model().bounces, vec![0.75, 1.75, 2.75, 3.75]);
+    }
+}
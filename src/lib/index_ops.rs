@@ -1,4 +1,4 @@
-//! Traits of indexed collections, and their implementations for [`Vec`].
+//! Traits of indexed collections, and their implementations for [`Vec`], slices and arrays.
 //!
 //! [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
 
@@ -16,6 +16,18 @@ impl<Item> Length for Vec<Item> {
     }
 }
 
+impl<Item> Length for [Item] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<Item, const N: usize> Length for [Item; N] {
+    fn length(&self) -> usize {
+        N
+    }
+}
+
 /// Types that can swap elements
 pub trait Swap<Item> : IndexMut<usize, Output=Item> {
     /// Swaps two elements in the slice.
@@ -34,13 +46,44 @@ impl<Item> Swap<Item> for Vec<Item> {
     }
 }
 
+impl<Item> Swap<Item> for [Item] {
+    fn swap(&mut self, a: usize, b: usize) {
+        <[Item]>::swap(self, a, b);
+    }
+}
+
+impl<Item, const N: usize> Swap<Item> for [Item; N] {
+    fn swap(&mut self, a: usize, b: usize) {
+        <[Item]>::swap(self, a, b);
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Length;
+    use super::{Length, Swap};
 
     #[test]
     fn vec_length() {
         let vec = vec![1, 2, 3, 4, 5];
         assert_eq!(vec.len(), vec.length());
     }
+
+    #[test]
+    fn slice_length_and_swap() {
+        let mut vec = vec![1, 2, 3, 4, 5];
+        {
+            let slice = &mut vec[1..4];
+            assert_eq!(Length::length(slice), 3);
+            Swap::swap(slice, 0, 2);
+        }
+        assert_eq!(vec, vec![1, 4, 3, 2, 5]);
+    }
+
+    #[test]
+    fn array_length_and_swap() {
+        let mut array = [1, 2, 3];
+        assert_eq!(Length::length(&array), 3);
+        Swap::swap(&mut array, 0, 2);
+        assert_eq!(array, [3, 2, 1]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,516 @@
+//! Indexed priority queues, as presented in the course: [`IndexMinPQ`] and [`IndexMaxPQ`] associate
+//! a key with each integer index from 0 to (excluding) their capacity, and allow to change the key
+//! of an index that is in the queue.
+//!
+//! The keys are ordered in a binary heap of indices `pq`, with the inverse array `qp`, that holds
+//! the position in the heap of each index. The heap is ordered with the swim and sink operations
+//! of [`max_pq`].
+//!
+//! [`IndexMinPQ`]: struct.IndexMinPQ.html
+//! [`IndexMaxPQ`]: struct.IndexMaxPQ.html
+//! [`max_pq`]: ../max_pq/index.html
+//!
+//! # Example
+//! A multiway merge of sorted streams: the queue holds the next element of each stream, indexed by
+//! the stream.
+//! ```
+//! use algorithms::index_pq::IndexMinPQ;
+//!
+//! let mut streams = vec![vec!["A", "B", "C", "F", "G", "I", "I", "Z"].into_iter(),
+//!                        vec!["B", "D", "H", "P", "Q", "Q"].into_iter(),
+//!                        vec!["A", "B", "E", "F", "J", "N"].into_iter()];
+//!
+//! let mut pq = IndexMinPQ::new(streams.len());
+//! for (i, stream) in streams.iter_mut().enumerate() {
+//!     if let Some(key) = stream.next() {
+//!         pq.insert(i, key);
+//!     }
+//! }
+//!
+//! let mut merged = vec![];
+//! while let Some((i, key)) = pq.del_min() {
+//!     merged.push(key);
+//!     if let Some(next) = streams[i].next() {
+//!         pq.insert(i, next);
+//!     }
+//! }
+//! assert_eq!(merged.concat(), "AABBBCDEFFGHIIJNPQQZ");
+//! ```
+
+use std::cmp::Ordering;
+use std::ops::{Index, IndexMut};
+use ::binary_heap::{Compare, Natural, Reversed};
+use ::index_ops::{Length, Swap};
+use ::max_pq::{sink, swim};
+
+/// the heap of indices of an indexed priority queue, with the largest key in the order C on top
+#[derive(Clone, Debug)]
+struct IndexHeap<Key, C> {
+    /// the indices, in heap order
+    pq: Vec<usize>,
+    /// the position of each index in pq, if the index is in the queue
+    qp: Vec<Option<usize>>,
+    /// the key of each index, if the index is in the queue
+    keys: Vec<Option<Key>>,
+    compare: C,
+}
+
+/// a view of the heap, that indexes the keys by position in the heap, and updates qp when it swaps
+/// positions, so that swim and sink can order it
+struct HeapView<'a, Key>
+    where Key: 'a {
+    pq: &'a mut Vec<usize>,
+    qp: &'a mut Vec<Option<usize>>,
+    keys: &'a mut Vec<Option<Key>>,
+}
+
+impl<'a, Key> Index<usize> for HeapView<'a, Key> {
+    type Output = Key;
+
+    fn index(&self, position: usize) -> &Key {
+        self.keys[self.pq[position]].as_ref().unwrap()
+    }
+}
+
+impl<'a, Key> IndexMut<usize> for HeapView<'a, Key> {
+    fn index_mut(&mut self, position: usize) -> &mut Key {
+        self.keys[self.pq[position]].as_mut().unwrap()
+    }
+}
+
+impl<'a, Key> Length for HeapView<'a, Key> {
+    fn length(&self) -> usize {
+        self.pq.len()
+    }
+}
+
+impl<'a, Key> Swap<Key> for HeapView<'a, Key> {
+    fn swap(&mut self, a: usize, b: usize) {
+        self.pq.swap(a, b);
+        self.qp[self.pq[a]] = Some(a);
+        self.qp[self.pq[b]] = Some(b);
+    }
+}
+
+impl<Key, C> IndexHeap<Key, C>
+    where C: Compare<Key> {
+    fn new(capacity: usize, compare: C) -> IndexHeap<Key, C> {
+        IndexHeap {
+            pq: Vec::with_capacity(capacity),
+            qp: vec![None; capacity],
+            keys: (0..capacity).map(|_| None).collect(),
+            compare,
+        }
+    }
+
+    fn check_index(&self, i: usize) {
+        assert!(i < self.qp.len(), "index {} is out of range for a capacity of {}", i, self.qp.len());
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.check_index(i);
+        self.qp[i].is_some()
+    }
+
+    /// the position of the index in the heap
+    fn position(&self, i: usize) -> usize {
+        self.check_index(i);
+        match self.qp[i] {
+            Some(position) => position,
+            None => panic!("index {} is not in the priority queue", i),
+        }
+    }
+
+    fn key_of(&self, i: usize) -> Option<&Key> {
+        self.check_index(i);
+        self.keys[i].as_ref()
+    }
+
+    fn top(&self) -> Option<(usize, &Key)> {
+        self.pq.first().map(|i| (*i, self.keys[*i].as_ref().unwrap()))
+    }
+
+    /// compares the key with the key of the index
+    fn compare_to_key_of(&self, key: &Key, i: usize) -> Ordering {
+        self.compare.compare(key, self.keys[i].as_ref().unwrap())
+    }
+
+    fn swim(&mut self, position: usize) {
+        let compare = &self.compare;
+        let mut view = HeapView { pq: &mut self.pq, qp: &mut self.qp, keys: &mut self.keys };
+        swim(&mut view, position, &mut |a: &Key, b: &Key| compare.compare(a, b) == Ordering::Less);
+    }
+
+    fn sink(&mut self, position: usize) {
+        let compare = &self.compare;
+        let mut view = HeapView { pq: &mut self.pq, qp: &mut self.qp, keys: &mut self.keys };
+        sink(&mut view, position, &mut |a: &Key, b: &Key| compare.compare(a, b) == Ordering::Less);
+    }
+
+    fn insert(&mut self, i: usize, key: Key) {
+        assert!(!self.contains(i), "index {} is already in the priority queue", i);
+        let position = self.pq.len();
+        self.pq.push(i);
+        self.qp[i] = Some(position);
+        self.keys[i] = Some(key);
+        self.swim(position);
+    }
+
+    /// replaces the key of the index, and returns the old one
+    fn change_key(&mut self, i: usize, key: Key) -> Key {
+        let position = self.position(i);
+        let old = self.keys[i].replace(key).unwrap();
+        self.swim(position);
+        self.sink(position);
+        old
+    }
+
+    fn delete(&mut self, i: usize) -> Key {
+        let position = self.position(i);
+        let last = self.pq.len() - 1;
+        {
+            let mut view = HeapView { pq: &mut self.pq, qp: &mut self.qp, keys: &mut self.keys };
+            view.swap(position, last);
+        }
+        self.pq.pop();
+        self.qp[i] = None;
+        if position < last {
+            self.swim(position);
+            self.sink(position);
+        }
+        self.keys[i].take().unwrap()
+    }
+
+    fn del_top(&mut self) -> Option<(usize, Key)> {
+        self.pq.first().cloned().map(|i| (i, self.delete(i)))
+    }
+}
+
+/// an indexed priority queue, with the smallest key on top
+#[derive(Clone, Debug)]
+pub struct IndexMinPQ<Key> {
+    heap: IndexHeap<Key, Reversed<Natural>>,
+}
+
+/// an indexed priority queue, with the largest key on top
+#[derive(Clone, Debug)]
+pub struct IndexMaxPQ<Key> {
+    heap: IndexHeap<Key, Natural>,
+}
+
+impl<Key> IndexMinPQ<Key>
+    where Key: PartialOrd {
+    /// an empty queue for the indices from 0 to (excluding) capacity
+    pub fn new(capacity: usize) -> IndexMinPQ<Key> {
+        IndexMinPQ { heap: IndexHeap::new(capacity, Reversed(Natural)) }
+    }
+
+    /// Associates the key with the index i.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or already in the queue.
+    pub fn insert(&mut self, i: usize, key: Key) {
+        self.heap.insert(i, key);
+    }
+
+    /// whether the index i is in the queue
+    pub fn contains(&self, i: usize) -> bool {
+        self.heap.contains(i)
+    }
+
+    /// the key associated with the index i, if it is in the queue
+    pub fn key_of(&self, i: usize) -> Option<&Key> {
+        self.heap.key_of(i)
+    }
+
+    /// the index with the smallest key, if the queue is not empty
+    pub fn min_index(&self) -> Option<usize> {
+        self.heap.top().map(|(i, _)| i)
+    }
+
+    /// the smallest key, if the queue is not empty
+    pub fn min_key(&self) -> Option<&Key> {
+        self.heap.top().map(|(_, key)| key)
+    }
+
+    /// removes the smallest key, and returns it together with its index, if the queue is not empty
+    pub fn del_min(&mut self) -> Option<(usize, Key)> {
+        self.heap.del_top()
+    }
+
+    /// Changes the key associated with the index i, and returns the old key.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or not in the queue.
+    pub fn change_key(&mut self, i: usize, key: Key) -> Key {
+        self.heap.change_key(i, key)
+    }
+
+    /// Decreases the key associated with the index i, and returns the old key.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or not in the queue, or if the key is not less than its key.
+    pub fn decrease_key(&mut self, i: usize, key: Key) -> Key {
+        let position = self.heap.position(i);
+        assert!(self.heap.compare_to_key_of(&key, i) == Ordering::Greater, "the key does not decrease the key of index {}", i);
+        let old = self.heap.keys[i].replace(key).unwrap();
+        self.heap.swim(position);
+        old
+    }
+
+    /// Increases the key associated with the index i, and returns the old key.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or not in the queue, or if the key is not greater than its key.
+    pub fn increase_key(&mut self, i: usize, key: Key) -> Key {
+        let position = self.heap.position(i);
+        assert!(self.heap.compare_to_key_of(&key, i) == Ordering::Less, "the key does not increase the key of index {}", i);
+        let old = self.heap.keys[i].replace(key).unwrap();
+        self.heap.sink(position);
+        old
+    }
+
+    /// Removes the index i and its key from the queue, and returns the key.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or not in the queue.
+    pub fn delete(&mut self, i: usize) -> Key {
+        self.heap.delete(i)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.pq.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.heap.pq.len()
+    }
+}
+
+impl<Key> IndexMaxPQ<Key>
+    where Key: PartialOrd {
+    /// an empty queue for the indices from 0 to (excluding) capacity
+    pub fn new(capacity: usize) -> IndexMaxPQ<Key> {
+        IndexMaxPQ { heap: IndexHeap::new(capacity, Natural) }
+    }
+
+    /// Associates the key with the index i.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or already in the queue.
+    pub fn insert(&mut self, i: usize, key: Key) {
+        self.heap.insert(i, key);
+    }
+
+    /// whether the index i is in the queue
+    pub fn contains(&self, i: usize) -> bool {
+        self.heap.contains(i)
+    }
+
+    /// the key associated with the index i, if it is in the queue
+    pub fn key_of(&self, i: usize) -> Option<&Key> {
+        self.heap.key_of(i)
+    }
+
+    /// the index with the largest key, if the queue is not empty
+    pub fn max_index(&self) -> Option<usize> {
+        self.heap.top().map(|(i, _)| i)
+    }
+
+    /// the largest key, if the queue is not empty
+    pub fn max_key(&self) -> Option<&Key> {
+        self.heap.top().map(|(_, key)| key)
+    }
+
+    /// removes the largest key, and returns it together with its index, if the queue is not empty
+    pub fn del_max(&mut self) -> Option<(usize, Key)> {
+        self.heap.del_top()
+    }
+
+    /// Changes the key associated with the index i, and returns the old key.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or not in the queue.
+    pub fn change_key(&mut self, i: usize, key: Key) -> Key {
+        self.heap.change_key(i, key)
+    }
+
+    /// Decreases the key associated with the index i, and returns the old key.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or not in the queue, or if the key is not less than its key.
+    pub fn decrease_key(&mut self, i: usize, key: Key) -> Key {
+        let position = self.heap.position(i);
+        assert!(self.heap.compare_to_key_of(&key, i) == Ordering::Less, "the key does not decrease the key of index {}", i);
+        let old = self.heap.keys[i].replace(key).unwrap();
+        self.heap.sink(position);
+        old
+    }
+
+    /// Increases the key associated with the index i, and returns the old key.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or not in the queue, or if the key is not greater than its key.
+    pub fn increase_key(&mut self, i: usize, key: Key) -> Key {
+        let position = self.heap.position(i);
+        assert!(self.heap.compare_to_key_of(&key, i) == Ordering::Greater, "the key does not increase the key of index {}", i);
+        let old = self.heap.keys[i].replace(key).unwrap();
+        self.heap.swim(position);
+        old
+    }
+
+    /// Removes the index i and its key from the queue, and returns the key.
+    ///
+    /// # Panics
+    /// Panics if i is out of range, or not in the queue.
+    pub fn delete(&mut self, i: usize) -> Key {
+        self.heap.delete(i)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.pq.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.heap.pq.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
+    use ::binary_heap::Compare;
+    use super::{IndexHeap, IndexMinPQ, IndexMaxPQ};
+
+    /// whether the heap is ordered, and pq and qp are inverse
+    fn is_consistent<Key, C>(heap: &IndexHeap<Key, C>) -> bool
+        where C: Compare<Key> {
+        let ordered = (1..heap.pq.len()).all(|k| {
+            heap.compare.compare(heap.keys[heap.pq[(k - 1) / 2]].as_ref().unwrap(), heap.keys[heap.pq[k]].as_ref().unwrap())
+                != ::std::cmp::Ordering::Less
+        });
+        let inverse = heap.pq.iter().enumerate().all(|(position, i)| heap.qp[*i] == Some(position));
+        let contained = heap.qp.iter().filter(|p| p.is_some()).count() == heap.pq.len()
+            && heap.keys.iter().filter(|k| k.is_some()).count() == heap.pq.len();
+        ordered && inverse && contained
+    }
+
+    /// the smallest key in the oracle
+    fn oracle_min(oracle: &[Option<u32>]) -> Option<u32> {
+        oracle.iter().filter_map(|key| *key).min()
+    }
+
+    #[test]
+    fn random_operations_like_an_oracle() {
+        const CAPACITY: usize = 20;
+        let mut rng = thread_rng();
+        let mut pq = IndexMinPQ::new(CAPACITY);
+        let mut oracle: Vec<Option<u32>> = vec![None; CAPACITY];
+
+        for _ in 0..5000 {
+            let i = rng.gen_range(0, CAPACITY);
+            let key = rng.gen_range(0, 50);
+            match rng.gen_range(0, 4) {
+                0 => if oracle[i].is_none() {
+                    pq.insert(i, key);
+                    oracle[i] = Some(key);
+                },
+                1 => if let Some(old) = oracle[i] {
+                    assert_eq!(pq.change_key(i, key), old);
+                    oracle[i] = Some(key);
+                },
+                2 => if oracle[i].is_some() {
+                    assert_eq!(Some(pq.delete(i)), oracle[i].take());
+                },
+                _ => {
+                    let min = pq.del_min();
+                    assert_eq!(min.map(|(_, key)| key), oracle_min(&oracle));
+                    if let Some((i, _)) = min {
+                        oracle[i] = None;
+                    }
+                }
+            }
+            assert!(is_consistent(&pq.heap));
+            assert_eq!(pq.min_key().cloned(), oracle_min(&oracle));
+            assert_eq!(pq.size(), oracle.iter().filter(|key| key.is_some()).count());
+            assert!((0..CAPACITY).all(|i| pq.contains(i) == oracle[i].is_some() && pq.key_of(i) == oracle[i].as_ref()));
+        }
+    }
+
+    #[test]
+    fn min_pq() {
+        let mut pq = IndexMinPQ::new(10);
+        assert!(pq.is_empty());
+        assert_eq!(pq.min_index(), None);
+        pq.insert(3, 30);
+        pq.insert(5, 50);
+        pq.insert(7, 70);
+        assert_eq!(pq.min_index(), Some(3));
+
+        assert_eq!(pq.decrease_key(7, 10), 70);
+        assert_eq!(pq.min_index(), Some(7));
+        assert_eq!(pq.increase_key(7, 60), 10);
+        assert_eq!(pq.min_index(), Some(3));
+        assert_eq!(pq.key_of(7), Some(&60));
+
+        assert_eq!(pq.del_min(), Some((3, 30)));
+        assert_eq!(pq.del_min(), Some((5, 50)));
+        assert_eq!(pq.del_min(), Some((7, 60)));
+        assert_eq!(pq.del_min(), None);
+    }
+
+    #[test]
+    fn max_pq() {
+        let mut pq = IndexMaxPQ::new(10);
+        pq.insert(3, 30);
+        pq.insert(5, 50);
+        pq.insert(7, 70);
+        assert_eq!(pq.max_index(), Some(7));
+        assert_eq!(pq.max_key(), Some(&70));
+
+        assert_eq!(pq.decrease_key(7, 10), 70);
+        assert_eq!(pq.max_index(), Some(5));
+        assert_eq!(pq.increase_key(3, 90), 30);
+        assert_eq!(pq.max_index(), Some(3));
+        assert_eq!(pq.change_key(5, 5), 50);
+        assert!(is_consistent(&pq.heap));
+
+        assert_eq!(pq.delete(7), 10);
+        assert!(!pq.contains(7));
+        assert_eq!(pq.del_max(), Some((3, 90)));
+        assert_eq!(pq.del_max(), Some((5, 5)));
+        assert!(pq.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "the key does not decrease the key of index 1")]
+    fn decrease_key_must_decrease() {
+        let mut pq = IndexMinPQ::new(2);
+        pq.insert(1, 5);
+        pq.decrease_key(1, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 1 is already in the priority queue")]
+    fn insert_twice() {
+        let mut pq = IndexMaxPQ::new(2);
+        pq.insert(1, 5);
+        pq.insert(1, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 0 is not in the priority queue")]
+    fn change_key_of_missing_index() {
+        let mut pq: IndexMinPQ<u32> = IndexMinPQ::new(2);
+        pq.change_key(0, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 2 is out of range for a capacity of 2")]
+    fn index_out_of_range() {
+        let pq: IndexMinPQ<u32> = IndexMinPQ::new(2);
+        pq.contains(2);
+    }
+}
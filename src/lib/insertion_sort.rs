@@ -1,4 +1,4 @@
-//! implements the selection sort algorithm
+//! implements the insertion sort algorithm
 //! # Example
 //! ```
 //! use algorithms::insertion_sort::insertion_sort;
@@ -8,23 +8,52 @@
 //! assert_eq!(vec, vec![1,2,3,4,5]);
 //! ```
 
+use std::cmp::Ordering;
 use std::ops::{Index, IndexMut};
 use ::index_ops::{Length, Swap};
 
-/// Sort types that implement [`Index`],  [`IndexMut`], [`Length`] and [`Swap`] with the selection sort algorithm.
+/// Sort types that implement [`Index`],  [`IndexMut`], [`Length`] and [`Swap`] with the insertion sort algorithm.
+///
+/// Insertion sort is stable: an element is only exchanged with a larger element to its left.
 ///
 /// [`Index`]: https://doc.rust-lang.org/std/ops/trait.Index.html
 /// [`IndexMut`]: https://doc.rust-lang.org/std/ops/trait.IndexMut.html
 /// [`Length`]: ../index_ops/trait.Length.html
 /// [`Swap`]: ../index_ops/trait.Swap.html
 pub fn insertion_sort<Coll, Item>(vec: &mut Coll)
-    where Coll: Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
           Item: PartialOrd {
+    insertion_sort_by_less(vec, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// Sorts like [`insertion_sort`], in the order defined by the compare function.
+///
+/// [`insertion_sort`]: fn.insertion_sort.html
+pub fn insertion_sort_by<Coll, Item, F>(vec: &mut Coll, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    insertion_sort_by_less(vec, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts like [`insertion_sort`], in the order of the keys that the key function extracts.
+///
+/// [`insertion_sort`]: fn.insertion_sort.html
+pub fn insertion_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    insertion_sort_by_less(vec, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+/// the insertion sort algorithm, where less decides if one element is less than another
+pub(crate) fn insertion_sort_by_less<Coll, Item, F>(vec: &mut Coll, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     let size = vec.length();
     if size > 1 {
         for i in 1..size {
             let mut j = i;
-            while j > 0 && vec[j] < vec[j - 1] {
+            while j > 0 && less(&vec[j], &vec[j - 1]) {
                 vec.swap(j, j - 1);
                 j = j - 1;
             }
@@ -37,8 +66,9 @@ mod test {
     extern crate rand;
 
     use self::rand::thread_rng;
-    use super::insertion_sort;
-    use ::is_sorted::{is_sorted, sort_some};
+    use super::{insertion_sort, insertion_sort_by, insertion_sort_by_key};
+    use ::is_sorted::{is_sorted, sort_some, sort_some_by, sort_some_slices};
+    use ::stability::sorts_stably;
 
     #[test]
     fn small_example() {
@@ -52,4 +82,29 @@ mod test {
         let mut rng = thread_rng();
         sort_some::<u32>(&mut rng, insertion_sort);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn insertion_sort_slices() {
+        let mut rng = thread_rng();
+        sort_some_slices::<u32>(&mut rng, insertion_sort);
+
+        let mut array = [3, 2, 4, 5, 1];
+        insertion_sort(&mut array);
+        assert_eq!(array, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn insertion_sort_by_some() {
+        let mut rng = thread_rng();
+        sort_some_by(&mut rng,
+                     |slice| insertion_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| insertion_sort_by_key(slice, |pair| pair.1));
+    }
+
+    #[test]
+    fn insertion_sort_is_stable() {
+        let mut rng = thread_rng();
+        assert!(sorts_stably(&mut rng, insertion_sort));
+        assert!(sorts_stably(&mut rng, |vec| insertion_sort_by_key(vec, |indexed| indexed.key)));
+    }
+}
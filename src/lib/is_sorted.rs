@@ -5,7 +5,7 @@ use std::ops::Index;
 use ::index_ops::Length;
 
 pub fn is_sorted<Coll, Item>(vec: &Coll) -> bool
-    where Coll: Index<usize, Output=Item> + Length,
+    where Coll: ?Sized + Index<usize, Output=Item> + Length,
           Item: PartialOrd {
     let len = vec.length();
     if len > 1 {
@@ -31,6 +31,36 @@ pub fn sort_some<Item>(rng: &mut ThreadRng, sorting_function: fn(&mut Vec<Item>)
     }
 }
 
+/// sorts the middle of some vectors as a slice, and checks that the rest is left alone
+pub fn sort_some_slices<Item>(rng: &mut ThreadRng, sorting_function: fn(&mut [Item]) -> ())
+    where Item: Clone + PartialOrd,
+          rand::distributions::Standard: rand::distributions::Distribution<Item> {
+    for size in (0..20).map(|x| x * x) {
+        let vec: Vec<Item> = (0..size + 6).map(|_| rng.gen()).collect();
+        let mut sorted = vec.clone();
+        sorting_function(&mut sorted[3..size + 3]);
+        assert!(is_sorted(&sorted[3..size + 3]));
+        assert!(sorted[..3] == vec[..3]);
+        assert!(sorted[size + 3..] == vec[size + 3..]);
+    }
+}
+
+/// sorts some vectors in descending order with a compare function, and some pairs by their
+/// second element with a key function
+pub fn sort_some_by(rng: &mut ThreadRng,
+                    sort_descending: fn(&mut [u32]) -> (),
+                    sort_by_second: fn(&mut [(u32, u32)]) -> ()) {
+    for size in (0..20).map(|x| x * x) {
+        let mut vec: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+        sort_descending(&mut vec);
+        assert!((1..size).all(|i| vec[i - 1] >= vec[i]));
+
+        let mut pairs: Vec<(u32, u32)> = (0..size).map(|_| (rng.gen(), rng.gen_range(0, 10))).collect();
+        sort_by_second(&mut pairs);
+        assert!((1..size).all(|i| pairs[i - 1].1 <= pairs[i].1));
+    }
+}
+
 #[test]
 fn test_is_sorted() {
     assert!(is_sorted(&vec![1, 2, 5, 8, 17, 26]));
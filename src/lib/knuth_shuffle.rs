@@ -1,10 +1,12 @@
 extern crate rand;
 
 use self::rand::{ThreadRng, Rng};
+use ::index_ops::{Length, Swap};
 
-/// randomly shuffles a vector with the knuth shuffle algorithm
-pub fn knuth_shuffle<Item>(vec: &mut Vec<Item>, rng: &mut ThreadRng) {
-    let size = vec.len();
+/// randomly shuffles a collection with the knuth shuffle algorithm
+pub fn knuth_shuffle<Coll, Item>(vec: &mut Coll, rng: &mut ThreadRng)
+    where Coll: ?Sized + Length + Swap<Item> {
+    let size = vec.length();
 
     if size > 1 {
         for i in 1..size {
@@ -0,0 +1,319 @@
+//! A leftist heap: a heap-ordered binary tree, in which the rank of each left child is at least
+//! the rank of its sibling. The rank of a node is the length of its right spine, the path of right
+//! children down from it, so the right spine of a heap of N nodes is at most lg(N + 1) long.
+//!
+//! Two heaps are melded by merging their right spines, like two sorted lists, and swapping the
+//! children where the rank of the left one gets too small. That takes logarithmic time in the
+//! worst case. Inserting melds a heap of one node, and deleting the maximum melds the children of
+//! the root.
+//!
+//! # Example
+//! ```
+//! use algorithms::leftist_heap::LeftistHeap;
+//! use algorithms::max_pq::{Meld, MaxPQ};
+//!
+//! let mut heap = LeftistHeap::new();
+//! heap.heap_insert(3);
+//! heap.heap_insert(7);
+//!
+//! let mut other = LeftistHeap::new();
+//! other.heap_insert(5);
+//! heap.meld(other);
+//!
+//! assert_eq!(heap.del_max(), Some(7));
+//! assert_eq!(heap.del_max(), Some(5));
+//! assert_eq!(heap.del_max(), Some(3));
+//! assert_eq!(heap.del_max(), None);
+//! ```
+
+use std::fmt;
+use std::mem;
+use ::max_pq::{MaxPQ, Meld};
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    item: T,
+    rank: usize,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// a leftist heap, with the largest item at the root
+pub struct LeftistHeap<T> {
+    root: Link<T>,
+    size: usize,
+}
+
+fn rank<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.rank)
+}
+
+// merges the right spines of the heaps. The recursion is at most as deep as the sum of the ranks.
+fn merge<T>(a: Link<T>, b: Link<T>) -> Link<T>
+    where T: PartialOrd {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.item < b.item {
+                mem::swap(&mut a, &mut b);
+            }
+            let right = a.right.take();
+            a.right = merge(right, Some(b));
+            if rank(&a.left) < rank(&a.right) {
+                mem::swap(&mut a.left, &mut a.right);
+            }
+            a.rank = rank(&a.right) + 1;
+            Some(a)
+        }
+    }
+}
+
+impl<T> LeftistHeap<T>
+    where T: PartialOrd {
+    pub fn new() -> LeftistHeap<T> {
+        LeftistHeap { root: None, size: 0 }
+    }
+
+    /// the largest item, if the heap is not empty
+    pub fn max(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.item)
+    }
+}
+
+impl<T> Default for LeftistHeap<T>
+    where T: PartialOrd {
+    fn default() -> LeftistHeap<T> {
+        LeftistHeap::new()
+    }
+}
+
+impl<T> Drop for LeftistHeap<T> {
+    // drops the nodes one by one, as the left paths can be long enough to overflow the stack when
+    // the tree is dropped recursively
+    fn drop(&mut self) {
+        let mut nodes: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = nodes.pop() {
+            nodes.extend(node.left.take());
+            nodes.extend(node.right.take());
+        }
+    }
+}
+
+impl<T> Clone for LeftistHeap<T>
+    where T: Clone {
+    // clones the nodes one by one, as cloning the tree recursively could overflow the stack. The path
+    // holds each node down to the one being cloned, with its clone, to which the clones of its left
+    // and then its right child are attached.
+    fn clone(&self) -> LeftistHeap<T> {
+        fn leaf<T>(node: &Node<T>) -> Box<Node<T>>
+            where T: Clone {
+            Box::new(Node { item: node.item.clone(), rank: node.rank, left: None, right: None })
+        }
+
+        let mut root = None;
+        let mut path: Vec<(&Node<T>, Box<Node<T>>)> = self.root.iter().map(|node| (&**node, leaf(node))).collect();
+        while let Some((node, clone)) = path.pop() {
+            let next = match (&node.left, &node.right) {
+                (Some(left), _) if clone.left.is_none() => Some(left),
+                (_, Some(right)) if clone.right.is_none() => Some(right),
+                _ => None,
+            };
+            match next {
+                Some(child) => {
+                    path.push((node, clone));
+                    path.push((child, leaf(child)));
+                }
+                None => match path.last_mut() {
+                    Some(&mut (parent, ref mut parent_clone)) => {
+                        if parent.left.is_some() && parent_clone.left.is_none() {
+                            parent_clone.left = Some(clone);
+                        } else {
+                            parent_clone.right = Some(clone);
+                        }
+                    }
+                    None => root = Some(clone),
+                },
+            }
+        }
+        LeftistHeap { root, size: self.size }
+    }
+}
+
+impl<T> fmt::Debug for LeftistHeap<T>
+    where T: fmt::Debug {
+    /// the items in preorder, from the largest one at the root on
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut nodes: Vec<&Node<T>> = self.root.iter().map(|node| &**node).collect();
+        while let Some(node) = nodes.pop() {
+            list.entry(&node.item);
+            nodes.extend(node.right.iter().map(|node| &**node));
+            nodes.extend(node.left.iter().map(|node| &**node));
+        }
+        list.finish()
+    }
+}
+
+impl<T> Meld for LeftistHeap<T>
+    where T: PartialOrd {
+    /// melds the other heap in logarithmic time
+    fn meld(&mut self, mut other: LeftistHeap<T>) {
+        self.root = merge(self.root.take(), other.root.take());
+        self.size += other.size;
+    }
+}
+
+impl<T> MaxPQ for LeftistHeap<T>
+    where T: PartialOrd {
+    type Item = T;
+
+    fn heap_insert(&mut self, item: T) {
+        let node = Box::new(Node { item, rank: 1, left: None, right: None });
+        self.root = merge(self.root.take(), Some(node));
+        self.size += 1;
+    }
+
+    fn del_max(&mut self) -> Option<T> {
+        self.root.take().map(|root| {
+            self.size -= 1;
+            let Node { item, left, right, .. } = *root;
+            self.root = merge(left, right);
+            item
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
+    use ::max_pq::{MaxPQ, Meld};
+    use super::{rank, Link, LeftistHeap};
+
+    /// whether the tree is heap ordered and leftist, and its ranks are right. Returns the number of
+    /// nodes, if so.
+    fn check<T>(link: &Link<T>) -> Option<usize>
+        where T: PartialOrd {
+        match *link {
+            None => Some(0),
+            Some(ref node) => {
+                let ordered = [&node.left, &node.right].iter()
+                    .all(|child| child.as_ref().is_none_or(|child| node.item >= child.item));
+                let leftist = rank(&node.left) >= rank(&node.right) && node.rank == rank(&node.right) + 1;
+                if ordered && leftist {
+                    Some(1 + check(&node.left)? + check(&node.right)?)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn insert_and_delete_like_a_sorted_vec() {
+        let mut rng = thread_rng();
+        let mut heap = LeftistHeap::new();
+        let mut oracle: Vec<u32> = vec![];
+        for _ in 0..1000 {
+            if rng.gen_range(0, 3) == 0 {
+                assert_eq!(heap.del_max(), oracle.pop());
+            } else {
+                let item = rng.gen_range(0, 100);
+                heap.heap_insert(item);
+                let index = oracle.iter().position(|x| *x > item).unwrap_or(oracle.len());
+                oracle.insert(index, item);
+            }
+            assert_eq!(check(&heap.root), Some(oracle.len()));
+            assert_eq!(heap.max(), oracle.last());
+            assert_eq!(heap.size(), oracle.len());
+        }
+    }
+
+    #[test]
+    fn meld_some() {
+        let mut rng = thread_rng();
+        for size in (0..20).map(|x| x * x) {
+            let mut heap = LeftistHeap::new();
+            let mut other = LeftistHeap::new();
+            let mut items: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+            for (i, item) in items.iter().enumerate() {
+                if i % 3 == 0 { heap.heap_insert(*item) } else { other.heap_insert(*item) }
+            }
+            heap.meld(other);
+            assert_eq!(check(&heap.root), Some(size));
+            assert_eq!(heap.size(), size);
+
+            items.sort();
+            while let Some(max) = heap.del_max() {
+                assert_eq!(Some(max), items.pop());
+            }
+            assert!(items.is_empty());
+        }
+    }
+
+    #[test]
+    fn rank_is_logarithmic() {
+        let mut heap = LeftistHeap::new();
+        for item in 0..1023 {
+            heap.heap_insert(item);
+        }
+        assert!(rank(&heap.root) <= 10);
+    }
+
+    #[test]
+    fn drop_a_deep_heap() {
+        // ascending inserts make each root the left child of the next one, a path of 100000 nodes
+        let mut heap = LeftistHeap::new();
+        for item in 0..100000 {
+            heap.heap_insert(item);
+        }
+        assert_eq!(heap.max(), Some(&99999));
+    }
+
+    #[test]
+    fn clone_a_deep_heap() {
+        let mut heap = LeftistHeap::new();
+        for item in 0..100000 {
+            heap.heap_insert(item);
+        }
+        // ascending inserts make a left path of 100000 nodes, like in drop_a_deep_heap
+        let mut clone = heap.clone();
+        assert_eq!(format!("{:?}", clone), format!("{:?}", heap));
+        assert_eq!(clone.del_max(), Some(99999));
+        assert_eq!(clone.del_max(), Some(99998));
+        assert_eq!(heap.max(), Some(&99999));
+    }
+
+    #[test]
+    fn clone_and_debug() {
+        let mut heap: LeftistHeap<u32> = LeftistHeap::new();
+        assert_eq!(format!("{:?}", heap.clone()), "[]");
+        for item in [3, 1, 4, 1, 5, 9, 2, 6] {
+            heap.heap_insert(item);
+        }
+        heap.del_max();
+
+        let mut clone = heap.clone();
+        assert_eq!(check(&clone.root), Some(7));
+        assert_eq!(format!("{:?}", clone), format!("{:?}", heap));
+        assert!(format!("{:?}", heap).starts_with("[6, "));
+        let mut items = vec![];
+        while let Some(item) = clone.del_max() {
+            assert_eq!(heap.del_max(), Some(item));
+            items.push(item);
+        }
+        assert_eq!(items, vec![6, 5, 4, 3, 2, 1, 1]);
+        assert!(heap.is_empty());
+    }
+}
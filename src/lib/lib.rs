@@ -16,6 +16,8 @@ pub mod performance;
 pub mod linked_list;
 
 pub mod deque;
+pub mod ring_deque;
+pub mod randomized_queue;
 
 pub mod selection_sort;
 
@@ -23,8 +25,14 @@ pub mod insertion_sort;
 
 pub mod step;
 
+pub mod sub_range;
+
 pub mod index_ops;
 
+pub mod counting;
+
+pub mod stability;
+
 pub mod shell_sort;
 
 pub mod merge_sort;
@@ -33,7 +41,21 @@ pub mod knuth_shuffle;
 
 pub mod quick_sort;
 
+pub mod parallel_sort;
+
+pub mod radix_sort;
+pub mod select;
+
 pub mod max_pq;
+pub mod binary_heap;
+pub mod index_pq;
+pub mod d_ary_heap;
+pub mod pairing_heap;
+pub mod leftist_heap;
+pub mod min_max_heap;
+
+pub mod event_simulation;
+pub mod collision_system;
 
 pub mod red_black_bst_2;
 
@@ -1,7 +1,17 @@
-//! Defines a trait representing a priority queue, and an implementation for [`Vec`].
+//! Defines traits representing priority queues, and an implementation of [`MaxPQ`] for [`Vec`].
+//! See [`binary_heap`] for a binary heap with a custom order, which implements both traits, and
+//! [`d_ary_heap`], [`pairing_heap`] and [`leftist_heap`] for other implementations of [`MaxPQ`].
 //!
+//! [`MaxPQ`]: trait.MaxPQ.html
 //! [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+//! [`binary_heap`]: ../binary_heap/index.html
+//! [`d_ary_heap`]: ../d_ary_heap/index.html
+//! [`pairing_heap`]: ../pairing_heap/index.html
+//! [`leftist_heap`]: ../leftist_heap/index.html
 
+use std::cmp::Ordering;
+use std::ops::{Index, IndexMut};
+use ::index_ops::{Length, Swap};
 
 // a trait representing a priority queue
 pub trait MaxPQ {
@@ -12,23 +22,39 @@ pub trait MaxPQ {
     fn size(&self) -> usize;
 }
 
+// a trait representing a priority queue, that can be melded with another one of its type
+pub trait Meld {
+    // moves all items of other into self
+    fn meld(&mut self, other: Self);
+}
+
+// a trait representing a priority queue, that deletes the minimum
+pub trait MinPQ {
+    type Item;
+    fn heap_insert(&mut self, item: Self::Item);
+    fn del_min(&mut self) -> Option<Self::Item>;
+    fn is_empty(&self) -> bool;
+    fn size(&self) -> usize;
+}
+
 // for a binary heap, implemented as a [`Vec`], as described in the course, but starting at index
 // 0, this function returns the parent index of the index k.
-fn parent(k: usize) -> usize {
+pub(crate) fn parent(k: usize) -> usize {
     ((k + 1) / 2) - 1
 }
 
 // for a binary heap, implemented as a [`Vec`], as described in the course, but starting at index
 // 0, this function returns the children indices of the index k, as a pair.
-fn children(k: usize) -> (usize, usize) {
+pub(crate) fn children(k: usize) -> (usize, usize) {
     (((k + 1) * 2) - 1, ((k + 1) * 2) - 1 + 1)
 }
 
 // swims the element at index k, as described in the course.
-fn swim<Item>(vec: &mut Vec<Item>, k: usize)
-    where Item: PartialOrd {
+pub(crate) fn swim<Coll, Item, F>(vec: &mut Coll, k: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     let mut i = k;
-    while i > 0 && vec[parent(i)] < vec[i] {
+    while i > 0 && less(&vec[parent(i)], &vec[i]) {
         vec.swap(parent(i), i);
         i = parent(i);
     }
@@ -36,13 +62,14 @@ fn swim<Item>(vec: &mut Vec<Item>, k: usize)
 
 // sinks the element at index k, as described in the course, only considerung the elements of the
 // vector up to (excluding) size.
-fn sink_with_size<Item>(vec: &mut Vec<Item>, k: usize, size: usize)
-    where Item: PartialOrd {
+pub(crate) fn sink_with_size<Coll, Item, F>(vec: &mut Coll, k: usize, size: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     let mut i = k;
     while (i + 1) * 2 <= size {
         let (child1, child2) = children(i);
-        let chosen_child = if child2 < size && vec[child1] < vec[child2] { child2 } else { child1 };
-        if vec[i] >= vec[chosen_child] {
+        let chosen_child = if child2 < size && less(&vec[child1], &vec[child2]) { child2 } else { child1 };
+        if !less(&vec[i], &vec[chosen_child]) {
             return;
         }
         vec.swap(i, chosen_child);
@@ -51,29 +78,121 @@ fn sink_with_size<Item>(vec: &mut Vec<Item>, k: usize, size: usize)
 }
 
 // sinks the element at index k, as described in the course.
-fn sink<Item>(vec: &mut Vec<Item>, k: usize)
-    where Item: PartialOrd {
-    let size = vec.len();
-    sink_with_size(vec, k, size);
+pub(crate) fn sink<Coll, Item, F>(vec: &mut Coll, k: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
+    sink_with_size(vec, k, size, less);
 }
 
-pub fn make_heap<Item>(vec: &mut Vec<Item>)
-    where Item: PartialOrd {
-    let size = vec.len();
+pub fn make_heap<Coll, Item>(vec: &mut Coll)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    make_heap_by_less(vec, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// makes a heap like [`make_heap`], with the largest element in the order defined by the compare
+/// function on top
+///
+/// [`make_heap`]: fn.make_heap.html
+pub fn make_heap_by<Coll, Item, F>(vec: &mut Coll, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    make_heap_by_less(vec, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// makes a heap like [`make_heap`], with the element with the largest key on top
+///
+/// [`make_heap`]: fn.make_heap.html
+pub fn make_heap_by_key<Coll, Item, K, F>(vec: &mut Coll, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    make_heap_by_less(vec, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+pub(crate) fn make_heap_by_less<Coll, Item, F>(vec: &mut Coll, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
     for k in (0..((size + 1 ) / 2)).rev() {
-        sink(vec, k);
+        sink(vec, k, less);
     }
 }
 
-pub fn heap_sort<Item>(vec: &mut Vec<Item>)
-    where Item: PartialOrd {
-    let size = vec.len();
+/// Sorts a collection with heap sort: it makes a heap of it like [`make_heap`], in linear time,
+/// and then exchanges the largest element with the last one of the heap, and sinks the element
+/// that took its place, until the heap is empty.
+///
+/// The sinking is bottom-up, as proposed by Floyd: the element that took the place of the largest
+/// one is usually small, so it is sunk all the way down along the larger children without
+/// comparing it, and then swum up to its place. That takes about N lg N compares instead of
+/// 2 N lg N.
+///
+/// Heap sort is not stable: exchanging the top of the heap with its last element changes the
+/// order of elements with equal keys.
+///
+/// [`make_heap`]: fn.make_heap.html
+pub fn heap_sort<Coll, Item>(vec: &mut Coll)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    heap_sort_by_less(vec, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// sorts like [`heap_sort`], in the order defined by the compare function
+///
+/// [`heap_sort`]: fn.heap_sort.html
+pub fn heap_sort_by<Coll, Item, F>(vec: &mut Coll, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    heap_sort_by_less(vec, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// sorts like [`heap_sort`], in the order of the keys that the key function extracts
+///
+/// [`heap_sort`]: fn.heap_sort.html
+pub fn heap_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    heap_sort_by_less(vec, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+pub(crate) fn heap_sort_by_less<Coll, Item, F>(vec: &mut Coll, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    make_heap_by_less(vec, less);
+    sort_down_by_less(vec, less);
+}
+
+// sorts a heap, by exchanging the largest element with the last one of the heap, and sinking the
+// element that took its place bottom-up.
+pub(crate) fn sort_down_by_less<Coll, Item, F>(vec: &mut Coll, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
     for k in (1..size).rev() {
         vec.swap(0, k);
-        sink_with_size(vec, 0, k);
+        let leaf = sink_to_bottom(vec, 0, k, less);
+        swim(vec, leaf, less);
     }
 }
 
+// sinks the element at index k to the bottom of the heap of the given size, along the larger
+// children, without comparing it with them. Returns the index where it ends up.
+fn sink_to_bottom<Coll, Item, F>(vec: &mut Coll, k: usize, size: usize, less: &mut F) -> usize
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let mut i = k;
+    while (i + 1) * 2 <= size {
+        let (child1, child2) = children(i);
+        let chosen_child = if child2 < size && less(&vec[child1], &vec[child2]) { child2 } else { child1 };
+        vec.swap(i, chosen_child);
+        i = chosen_child;
+    }
+    i
+}
+
 impl<Item> MaxPQ for Vec<Item>
     where Item: PartialOrd {
     type Item = Item;
@@ -81,7 +200,7 @@ impl<Item> MaxPQ for Vec<Item>
     fn heap_insert(&mut self, item: Item) {
         self.push(item);
         let size = self.len();
-        swim(self, size - 1);
+        swim(self, size - 1, &mut |a: &Item, b: &Item| a < b);
     }
 
     fn del_max(&mut self) -> Option<Item> {
@@ -91,7 +210,7 @@ impl<Item> MaxPQ for Vec<Item>
         } else {
             self.swap(0, size - 1);
             let rslt = self.pop();
-            sink(self, 0);
+            sink(self, 0, &mut |a: &Item, b: &Item| a < b);
             rslt
         }
     }
@@ -107,8 +226,13 @@ impl<Item> MaxPQ for Vec<Item>
 
 #[cfg(test)]
 mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
     use super::{MaxPQ, parent, make_heap, sink, heap_sort};
-    use ::is_sorted::is_sorted;
+    use super::{make_heap_by, make_heap_by_key, heap_sort_by, heap_sort_by_key};
+    use ::is_sorted::{is_sorted, sort_some, sort_some_by, sort_some_slices};
+    use ::stability::{indexed, is_stably_sorted, sorts_stably};
 
     pub fn is_binary_heap<Item>(vec: &Vec<Item>) -> bool
         where Item: PartialOrd {
@@ -196,43 +320,43 @@ mod tests {
         //       89,          4,
         //  78,     4,    9,    346,
         // 9, 3, 56, 2, 56, 2, 6
-        sink(&mut vec, 6);
+        sink(&mut vec, 6, &mut |a: &i32, b: &i32| a < b);
         //              1,
         //       89,          4,
         //  78,     4,    9,    346,
         // 9, 3, 56, 2, 56, 2, 6
         assert_eq!(vec, vec!(1, 89, 4, 78, 4, 9, 346, 9, 3, 56, 2, 56, 2, 6));
-        sink(&mut vec, 5);
+        sink(&mut vec, 5, &mut |a: &i32, b: &i32| a < b);
         //              1,
         //       89,          4,
         //  78,     4,   56,    346,
         // 9, 3, 56, 2, 9, 2, 6
         assert_eq!(vec, vec!(1, 89, 4, 78, 4, 56, 346, 9, 3, 56, 2, 9, 2, 6));
-        sink(&mut vec, 4);
+        sink(&mut vec, 4, &mut |a: &i32, b: &i32| a < b);
         //              1,
         //       89,         4,
         //  78,    56,  56,    346,
         // 9, 3, 4, 2, 9, 2, 6
         assert_eq!(vec, vec!(1, 89, 4, 78, 56, 56, 346, 9, 3, 4, 2, 9, 2, 6));
-        sink(&mut vec, 3);
+        sink(&mut vec, 3, &mut |a: &i32, b: &i32| a < b);
         //              1,
         //       89,         4,
         //  78,   56,    56,   346,
         // 9, 3, 4, 2, 9, 2, 6
         assert_eq!(vec, vec!(1, 89, 4, 78, 56, 56, 346, 9, 3, 4, 2, 9, 2, 6));
-        sink(&mut vec, 2);
+        sink(&mut vec, 2, &mut |a: &i32, b: &i32| a < b);
         //              1,
         //       89,       346,
         //  78,    56,  56,    6,
         // 9, 3, 4, 2, 9, 2, 4
         assert_eq!(vec, vec!(1, 89, 346, 78, 56, 56, 6, 9, 3, 4, 2, 9, 2, 4));
-        sink(&mut vec, 1);
+        sink(&mut vec, 1, &mut |a: &i32, b: &i32| a < b);
         //              1,
         //       89,       346,
         //  78,    56,  56,    6,
         // 9, 3, 4, 2, 9, 2, 4
         assert_eq!(vec, vec!(1, 89, 346, 78, 56, 56, 6, 9, 3, 4, 2, 9, 2, 4));
-        sink(&mut vec, 0);
+        sink(&mut vec, 0, &mut |a: &i32, b: &i32| a < b);
         //            346,
         //       89,       56,
         //  78,    56,   9,    6,
@@ -256,4 +380,75 @@ mod tests {
         heap_sort(&mut vec);
         assert!(is_sorted(&mut vec));
     }
+
+    #[test]
+    fn heap_sort_without_heap() {
+        // not a heap: heap sort makes the heap itself
+        let mut vec = vec!(1, 89, 4, 78, 4, 9, 346, 9, 3, 56, 2, 56, 2, 6);
+        assert!(!is_binary_heap(&vec));
+        heap_sort(&mut vec);
+        assert_eq!(vec, vec!(1, 2, 2, 3, 4, 4, 6, 9, 9, 56, 56, 78, 89, 346));
+    }
+
+    #[test]
+    fn heap_sort_some() {
+        let mut rng = thread_rng();
+        sort_some::<u32>(&mut rng, heap_sort);
+        sort_some::<u8>(&mut rng, heap_sort);
+        sort_some::<(u8, bool)>(&mut rng, heap_sort);
+    }
+
+    #[test]
+    fn heap_sort_like_std() {
+        let mut rng = thread_rng();
+        for size in (0..20).map(|x| x * x) {
+            let inputs: Vec<Vec<u32>> = vec![
+                (0..size).map(|_| rng.gen_range(0, 4)).collect(),
+                (0..size).collect(),
+                (0..size).rev().collect(),
+                (0..size).map(|i| if i % 2 == 0 { i } else { size - i }).collect(),
+            ];
+            for mut vec in inputs {
+                let mut expected = vec.clone();
+                expected.sort();
+                heap_sort(&mut vec);
+                assert_eq!(vec, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_heap_sort_slices() {
+        let mut rng = thread_rng();
+        sort_some_slices::<u32>(&mut rng, heap_sort);
+
+        let mut array = [1, 89, 4, 78, 4, 9, 346, 9, 3, 56, 2, 56, 2, 6];
+        heap_sort(&mut array);
+        assert_eq!(array, [1, 2, 2, 3, 4, 4, 6, 9, 9, 56, 56, 78, 89, 346]);
+    }
+
+    #[test]
+    fn test_heap_sort_by() {
+        let mut rng = thread_rng();
+        sort_some_by(&mut rng,
+                     |slice| heap_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| heap_sort_by_key(slice, |pair| pair.1));
+    }
+
+    #[test]
+    fn test_make_heap_by() {
+        let mut vec = vec!(1, 89, 4, 78, 4, 9, 346, 9, 3, 56, 2, 56, 2, 6);
+        make_heap_by(&mut vec, |a, b| b.cmp(a));
+        assert_eq!(vec[0], 1);
+        make_heap_by_key(&mut vec, |x| *x);
+        assert!(is_binary_heap(&vec));
+    }
+
+    #[test]
+    fn heap_sort_is_not_stable() {
+        let mut vec = indexed(vec![1, 1]);
+        heap_sort(&mut vec);
+        assert!(!is_stably_sorted(&vec));
+        assert!(!sorts_stably(&mut thread_rng(), heap_sort));
+    }
 }
@@ -1,63 +1,452 @@
-/// Sorts a vector with the merge-sort algorithm
-pub fn merge_sort<Item>(vec: &mut Vec<Item>)
-    where Item: PartialOrd + Copy {
-    let mut aux_vec = vec.clone();
-    let size = vec.len();
-    do_merge_sort(vec, &mut aux_vec, 0, size);
+//! implements the merge sort algorithm, with the improvements of the course: an insertion sort
+//! for small subarrays, no merge if the two halves are in order already, and no copying to the
+//! auxilary array, by switching the roles of the collection and the auxilary array on each level
+//! of the recursion.
+//!
+//! The elements are moved between the collection and the auxilary array, so they neither need to
+//! be `Copy` nor `Clone`.
+//!
+//! Besides the top-down [`merge_sort`], there are a [`bottom_up_merge_sort`] without recursion,
+//! a [`natural_merge_sort`], that merges the runs that are in the input already, and an
+//! [`insertion_merge_sort`], that insertion-sorts runs of a given size before merging them.
+//! All of them are stable.
+//!
+//! [`merge_sort`]: fn.merge_sort.html
+//! [`bottom_up_merge_sort`]: fn.bottom_up_merge_sort.html
+//! [`natural_merge_sort`]: fn.natural_merge_sort.html
+//! [`insertion_merge_sort`]: fn.insertion_merge_sort.html
+//!
+//! # Example
+//! ```
+//! use algorithms::merge_sort::merge_sort;
+//!
+//! let mut vec = vec![String::from("merge"), String::from("sort"), String::from("example")];
+//! merge_sort(&mut vec);
+//! assert_eq!(vec, vec!["example", "merge", "sort"]);
+//! ```
+
+use std::cmp::Ordering;
+use std::ops::{Index, IndexMut};
+use std::ptr;
+use ::index_ops::Length;
+
+/// subarrays of at most this size are sorted with insertion sort
+const CUTOFF: usize = 7;
+
+/// Sorts types that implement [`Index`], [`IndexMut`] and [`Length`] with the merge-sort algorithm
+///
+/// Merge sort is stable: merging takes the element from the left half, when two elements are
+/// equal. So are the other merge sorts of this module.
+///
+/// [`Index`]: https://doc.rust-lang.org/std/ops/trait.Index.html
+/// [`IndexMut`]: https://doc.rust-lang.org/std/ops/trait.IndexMut.html
+/// [`Length`]: ../index_ops/trait.Length.html
+pub fn merge_sort<Coll, Item>(vec: &mut Coll)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          Item: PartialOrd {
+    merge_sort_by_less(vec, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// Sorts like [`merge_sort`], in the order defined by the compare function.
+///
+/// [`merge_sort`]: fn.merge_sort.html
+pub fn merge_sort_by<Coll, Item, F>(vec: &mut Coll, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          F: FnMut(&Item, &Item) -> Ordering {
+    merge_sort_by_less(vec, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
 }
 
-/// recursivly merge-sort a vector, using an auxilary vector
-fn do_merge_sort<Item>(vec: &mut Vec<Item>, aux_vec: &mut Vec<Item>, lo: usize, hi: usize)
-    where Item: PartialOrd + Copy {
-    let mid = (lo + hi) / 2;
+/// Sorts like [`merge_sort`], in the order of the keys that the key function extracts.
+///
+/// [`merge_sort`]: fn.merge_sort.html
+pub fn merge_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    merge_sort_by_less(vec, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
 
-    if mid - lo > 1 {
-        do_merge_sort(vec, aux_vec, lo, mid);
+pub(crate) fn merge_sort_by_less<Coll, Item, F>(vec: &mut Coll, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
+    let mut aux_vec = Aux::copy_of(vec);
+    if size > 1 {
+        do_merge_sort(&mut aux_vec, &mut CollSlots(vec), 0, size, less);
     }
+}
 
-    if hi - mid > 1 {
-        do_merge_sort(vec, aux_vec, mid, hi)
+/// Merge-sorts the range from lo to (excluding) hi into dst. On entry, src and dst hold the same
+/// elements in that range; src is used as the auxilary array, and is left in an unspecified order.
+fn do_merge_sort<Src, Dst, F>(src: &mut Src, dst: &mut Dst, lo: usize, hi: usize, less: &mut F)
+    where Src: Slots,
+          Dst: Slots<Item=Src::Item>,
+          F: FnMut(&Src::Item, &Src::Item) -> bool {
+    if hi - lo <= CUTOFF {
+        insertion_sort(dst, lo, hi, less);
+        return;
     }
 
-    do_merge(vec, aux_vec, lo, hi, mid);
-}
+    let mid = lo + (hi - lo) / 2;
+    // sort the halves into src, using dst as the auxilary array
+    do_merge_sort(dst, src, lo, mid, less);
+    do_merge_sort(dst, src, mid, hi, less);
 
+    // the halves are in order already
+    if !src.less(mid, mid - 1, less) {
+        for k in lo..hi {
+            unsafe { ptr::copy_nonoverlapping(src.slot(k), dst.slot(k), 1) };
+        }
+        return;
+    }
 
-/// Merges two ranges of a vector. The first range is the range from the lo-th element to
-/// the (mid-1)th element. The second part of the vector is the range from the mid-th element to the
-/// (hi-1)th element.
-pub fn merge<Item>(vec: &mut Vec<Item>, mid: usize)
-    where Item: PartialOrd + Copy {
-    let mut aux_vec = vec.clone();
-    let size = vec.len();
-    do_merge(vec, &mut aux_vec, 0, size, mid)
+    Merge { src, dst, i: lo, mid, j: mid, hi, k: lo }.merge(less);
+}
+
+/// insertion-sorts the range from lo to (excluding) hi of the slots
+fn insertion_sort<Dst, F>(dst: &mut Dst, lo: usize, hi: usize, less: &mut F)
+    where Dst: Slots,
+          F: FnMut(&Dst::Item, &Dst::Item) -> bool {
+    for i in lo + 1..hi {
+        let mut j = i;
+        while j > lo && dst.less(j, j - 1, less) {
+            dst.swap(j, j - 1);
+            j -= 1;
+        }
+    }
 }
 
-/// Merges two ranges of a vector, using an auxilary vector. The first range is the range from the lo-th element to
-/// the (mid-1)th element. The second part of the vector is the range from the mid-th element to the
+/// Merges two ranges of a collection. The first range is the range from the lo-th element to
+/// the (mid-1)th element. The second part of the collection is the range from the mid-th element to the
 /// (hi-1)th element.
-fn do_merge<Item>(vec: &mut Vec<Item>, aux_vec: &mut Vec<Item>, lo: usize, hi: usize, mid: usize)
-    where Item: PartialOrd + Copy {
-    let mut i = lo;
-    let mut j = mid;
+pub fn merge<Coll, Item>(vec: &mut Coll, mid: usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          Item: PartialOrd {
+    let size = vec.length();
+    let mut aux_vec = Aux::with_capacity(size);
+    if 0 < mid && mid < size {
+        merge_range(&mut CollSlots(vec), &mut aux_vec, 0, mid, size, &mut |a: &Item, b: &Item| a < b);
+    }
+}
 
+/// Merges the sorted ranges lo..mid and mid..hi of the collection, by moving the range to the
+/// auxilary array, and merging it back. Both ranges must not be empty.
+fn merge_range<Coll, Item, F>(vec: &mut CollSlots<Coll>, aux_vec: &mut Aux<Item>, lo: usize, mid: usize, hi: usize, less: &mut F)
+    where Coll: ?Sized + IndexMut<usize, Output=Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    // the ranges are in order already
+    if !vec.less(mid, mid - 1, less) {
+        return;
+    }
     for k in lo..hi {
-        aux_vec[k] = vec[k];
+        unsafe { ptr::copy_nonoverlapping(vec.slot(k), aux_vec.slot(k), 1) };
     }
+    Merge { src: aux_vec, dst: vec, i: lo, mid, j: mid, hi, k: lo }.merge(less);
+}
 
-    for k in lo..hi {
-        if i >= mid {
-            vec[k] = aux_vec[j];
-            j = j + 1;
-        } else if j >= hi {
-            vec[k] = aux_vec[i];
-            i = i + 1;
-        } else if aux_vec[i] <= aux_vec[j] {
-            vec[k] = aux_vec[i];
-            i = i + 1;
-        } else {
-            vec[k] = aux_vec[j];
-            j = j + 1;
+/// Sorts like [`merge_sort`], but bottom-up: without recursion, it merges subarrays of size 1,
+/// then of size 2, 4 and so on. Merges of subarrays that are in order already are skipped, so
+/// sorted input takes linear time.
+///
+/// [`merge_sort`]: fn.merge_sort.html
+pub fn bottom_up_merge_sort<Coll, Item>(vec: &mut Coll)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          Item: PartialOrd {
+    bottom_up_merge_sort_by_less(vec, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// Sorts like [`bottom_up_merge_sort`], in the order defined by the compare function.
+///
+/// [`bottom_up_merge_sort`]: fn.bottom_up_merge_sort.html
+pub fn bottom_up_merge_sort_by<Coll, Item, F>(vec: &mut Coll, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          F: FnMut(&Item, &Item) -> Ordering {
+    bottom_up_merge_sort_by_less(vec, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts like [`bottom_up_merge_sort`], in the order of the keys that the key function extracts.
+///
+/// [`bottom_up_merge_sort`]: fn.bottom_up_merge_sort.html
+pub fn bottom_up_merge_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    bottom_up_merge_sort_by_less(vec, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn bottom_up_merge_sort_by_less<Coll, Item, F>(vec: &mut Coll, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          F: FnMut(&Item, &Item) -> bool {
+    insertion_merge_sort_by_less(vec, 1, less);
+}
+
+/// Sorts like [`bottom_up_merge_sort`], but insertion-sorts subarrays of the given size first,
+/// and then merges those. Like insertion sort and merge sort, it is stable.
+///
+/// # Panics
+/// Panics if `run_size` is 0.
+///
+/// [`bottom_up_merge_sort`]: fn.bottom_up_merge_sort.html
+pub fn insertion_merge_sort<Coll, Item>(vec: &mut Coll, run_size: usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          Item: PartialOrd {
+    insertion_merge_sort_by_less(vec, run_size, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// Sorts like [`insertion_merge_sort`], in the order defined by the compare function.
+///
+/// [`insertion_merge_sort`]: fn.insertion_merge_sort.html
+pub fn insertion_merge_sort_by<Coll, Item, F>(vec: &mut Coll, run_size: usize, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          F: FnMut(&Item, &Item) -> Ordering {
+    insertion_merge_sort_by_less(vec, run_size, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts like [`insertion_merge_sort`], in the order of the keys that the key function extracts.
+///
+/// [`insertion_merge_sort`]: fn.insertion_merge_sort.html
+pub fn insertion_merge_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, run_size: usize, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    insertion_merge_sort_by_less(vec, run_size, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn insertion_merge_sort_by_less<Coll, Item, F>(vec: &mut Coll, run_size: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          F: FnMut(&Item, &Item) -> bool {
+    assert!(run_size > 0, "the run size must not be 0");
+    let size = vec.length();
+    let mut aux_vec = Aux::with_capacity(size);
+    let mut slots = CollSlots(vec);
+
+    if run_size > 1 {
+        let mut lo = 0;
+        while lo < size {
+            let hi = size.min(lo + run_size);
+            insertion_sort(&mut slots, lo, hi, less);
+            lo = hi;
+        }
+    }
+
+    let mut width = run_size;
+    while width < size {
+        let mut lo = 0;
+        while lo + width < size {
+            let hi = size.min(lo + 2 * width);
+            merge_range(&mut slots, &mut aux_vec, lo, lo + width, hi, less);
+            lo = hi;
+        }
+        width *= 2;
+    }
+}
+
+/// Sorts like [`merge_sort`], but merges the runs that are in the input already: ascending runs,
+/// and strictly descending runs, that are reversed first. Input that consists of R runs takes
+/// time proportional to N lg R, so sorted input takes linear time.
+///
+/// [`merge_sort`]: fn.merge_sort.html
+pub fn natural_merge_sort<Coll, Item>(vec: &mut Coll)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          Item: PartialOrd {
+    natural_merge_sort_by_less(vec, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// Sorts like [`natural_merge_sort`], in the order defined by the compare function.
+///
+/// [`natural_merge_sort`]: fn.natural_merge_sort.html
+pub fn natural_merge_sort_by<Coll, Item, F>(vec: &mut Coll, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          F: FnMut(&Item, &Item) -> Ordering {
+    natural_merge_sort_by_less(vec, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts like [`natural_merge_sort`], in the order of the keys that the key function extracts.
+///
+/// [`natural_merge_sort`]: fn.natural_merge_sort.html
+pub fn natural_merge_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    natural_merge_sort_by_less(vec, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn natural_merge_sort_by_less<Coll, Item, F>(vec: &mut Coll, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
+    let mut slots = CollSlots(vec);
+
+    // the start of every run, followed by the size
+    let mut runs = vec![0];
+    while runs[runs.len() - 1] < size {
+        let lo = runs[runs.len() - 1];
+        let hi = find_run(&mut slots, lo, size, less);
+        runs.push(hi);
+    }
+
+    let mut aux_vec = Aux::with_capacity(size);
+    while runs.len() > 2 {
+        let mut merged = vec![0];
+        let mut i = 0;
+        while i + 2 < runs.len() {
+            merge_range(&mut slots, &mut aux_vec, runs[i], runs[i + 1], runs[i + 2], less);
+            merged.push(runs[i + 2]);
+            i += 2;
+        }
+        if i + 1 < runs.len() {
+            merged.push(runs[i + 1]);
+        }
+        runs = merged;
+    }
+}
+
+/// finds the run that starts at lo, and returns its end. A strictly descending run is reversed.
+fn find_run<Coll, Item, F>(vec: &mut CollSlots<Coll>, lo: usize, size: usize, less: &mut F) -> usize
+    where Coll: ?Sized + IndexMut<usize, Output=Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let mut hi = lo + 1;
+    if hi < size {
+        let descending = vec.less(hi, lo, less);
+        hi += 1;
+        while hi < size && vec.less(hi, hi - 1, less) == descending {
+            hi += 1;
+        }
+        if descending {
+            for k in 0..(hi - lo) / 2 {
+                vec.swap(lo + k, hi - 1 - k);
+            }
+        }
+    }
+    hi
+}
+
+/// The places that merge sort moves the elements between: either the collection to sort, or the
+/// auxilary array.
+///
+/// While merge sort runs, an element can be in both places. Only the collection owns the elements
+/// though, and it holds each element exactly once, whenever merge sort returns or panics.
+trait Slots {
+    type Item;
+
+    fn get(&self, k: usize) -> &Self::Item;
+
+    /// a pointer to write to. It is only valid until the next call of a method of the slots.
+    fn slot(&mut self, k: usize) -> *mut Self::Item;
+
+    /// whether the element at index a is less than the element at index b
+    fn less<F>(&self, a: usize, b: usize, less: &mut F) -> bool
+        where F: FnMut(&Self::Item, &Self::Item) -> bool {
+        less(self.get(a), self.get(b))
+    }
+
+    /// swaps the elements at the indices a and b
+    fn swap(&mut self, a: usize, b: usize) {
+        unsafe {
+            let item_a = ptr::read(self.slot(a));
+            let item_b = ptr::read(self.slot(b));
+            ptr::write(self.slot(a), item_b);
+            ptr::write(self.slot(b), item_a);
+        }
+    }
+}
+
+struct CollSlots<'a, Coll>(&'a mut Coll)
+    where Coll: 'a + ?Sized;
+
+impl<'a, Coll, Item> Slots for CollSlots<'a, Coll>
+    where Coll: ?Sized + IndexMut<usize, Output=Item> {
+    type Item = Item;
+
+    fn get(&self, k: usize) -> &Item {
+        &self.0[k]
+    }
+
+    fn slot(&mut self, k: usize) -> *mut Item {
+        &mut self.0[k]
+    }
+}
+
+/// the auxilary array. It never drops the elements it holds.
+struct Aux<Item> {
+    vec: Vec<Item>,
+}
+
+impl<Item> Aux<Item> {
+    /// an empty auxilary array, for the given number of elements
+    fn with_capacity(size: usize) -> Aux<Item> {
+        // the length stays 0, so that the elements are not dropped with the vector
+        Aux { vec: Vec::with_capacity(size) }
+    }
+
+    /// an auxilary array, that holds the same elements as the collection
+    fn copy_of<Coll>(vec: &mut Coll) -> Aux<Item>
+        where Coll: ?Sized + IndexMut<usize, Output=Item> + Length {
+        let size = vec.length();
+        let mut aux = Aux::with_capacity(size);
+        for k in 0..size {
+            unsafe { ptr::copy_nonoverlapping(&vec[k], aux.slot(k), 1) };
+        }
+        aux
+    }
+}
+
+impl<Item> Slots for Aux<Item> {
+    type Item = Item;
+
+    fn get(&self, k: usize) -> &Item {
+        unsafe { &*self.vec.as_ptr().add(k) }
+    }
+
+    fn slot(&mut self, k: usize) -> *mut Item {
+        unsafe { self.vec.as_mut_ptr().add(k) }
+    }
+}
+
+/// Merges the sorted ranges i..mid and j..hi of src into dst, starting at k.
+///
+/// If the compare function panics, dropping the merge moves the elements that are not merged yet
+/// to dst, so that dst holds all the elements of the range again.
+struct Merge<'a, Src, Dst>
+    where Src: 'a + Slots,
+          Dst: 'a + Slots<Item=Src::Item> {
+    src: &'a mut Src,
+    dst: &'a mut Dst,
+    i: usize,
+    mid: usize,
+    j: usize,
+    hi: usize,
+    k: usize,
+}
+
+impl<'a, Src, Dst> Merge<'a, Src, Dst>
+    where Src: Slots,
+          Dst: Slots<Item=Src::Item> {
+    fn merge<F>(mut self, less: &mut F)
+        where F: FnMut(&Src::Item, &Src::Item) -> bool {
+        while self.i < self.mid && self.j < self.hi {
+            // take from the left range on equal elements, to keep the sort stable
+            let from = if self.src.less(self.j, self.i, less) {
+                self.j += 1;
+                self.j - 1
+            } else {
+                self.i += 1;
+                self.i - 1
+            };
+            unsafe { ptr::copy_nonoverlapping(self.src.slot(from), self.dst.slot(self.k), 1) };
+            self.k += 1;
+        }
+        // dropping self moves the rest of the range that is not exhausted
+    }
+}
+
+impl<'a, Src, Dst> Drop for Merge<'a, Src, Dst>
+    where Src: Slots,
+          Dst: Slots<Item=Src::Item> {
+    fn drop(&mut self) {
+        for from in (self.i..self.mid).chain(self.j..self.hi) {
+            unsafe { ptr::copy_nonoverlapping(self.src.slot(from), self.dst.slot(self.k), 1) };
+            self.k += 1;
         }
     }
 }
@@ -66,10 +455,17 @@ fn do_merge<Item>(vec: &mut Vec<Item>, aux_vec: &mut Vec<Item>, lo: usize, hi: u
 mod test {
 extern crate rand;
 
-    use super::{merge, merge_sort};
+    use std::cmp::Ordering;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+    use super::{merge, merge_sort, merge_sort_by, merge_sort_by_key};
+    use super::{bottom_up_merge_sort, bottom_up_merge_sort_by, bottom_up_merge_sort_by_key};
+    use super::{natural_merge_sort, natural_merge_sort_by, natural_merge_sort_by_key};
+    use super::{insertion_merge_sort, insertion_merge_sort_by, insertion_merge_sort_by_key};
     use is_sorted::is_sorted;
-    use self::rand::thread_rng;
-    use ::is_sorted::sort_some;
+    use self::rand::{thread_rng, Rng};
+    use ::is_sorted::{sort_some, sort_some_by, sort_some_slices};
+    use ::stability::sorts_stably;
 
     #[test]
     fn merge_some() {
@@ -90,4 +486,137 @@ extern crate rand;
         let mut rng = thread_rng();
         sort_some::<u32>(&mut rng, merge_sort);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn merge_sort_slices() {
+        let mut rng = thread_rng();
+        sort_some_slices::<u32>(&mut rng, merge_sort);
+
+        let mut array = [7, 3, 5, 6, 2, 5, 1, 9];
+        merge_sort(&mut array);
+        assert_eq!(array, [1, 2, 3, 5, 5, 6, 7, 9]);
+    }
+
+    #[test]
+    fn merge_sort_by_some() {
+        let mut rng = thread_rng();
+        sort_some_by(&mut rng,
+                     |slice| merge_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| merge_sort_by_key(slice, |pair| pair.1));
+    }
+
+    #[test]
+    fn merge_sort_strings() {
+        let mut vec: Vec<String> = "the quick brown fox jumps over the lazy dog".split(' ').map(String::from).collect();
+        merge_sort(&mut vec);
+        assert_eq!(vec, vec!["brown", "dog", "fox", "jumps", "lazy", "over", "quick", "the", "the"]);
+    }
+
+    type Counted = (u32, Rc<()>);
+    type Compare<'a> = &'a mut dyn FnMut(&Counted, &Counted) -> Ordering;
+
+    // sorts with a compare function that panics, and checks that no element is lost or dropped twice
+    fn sort_panicking(sort: fn(&mut [Counted], Compare)) {
+        let mut rng = thread_rng();
+        let counter = Rc::new(());
+        for size in (1..20).map(|x| x * x) {
+            let mut vec: Vec<Counted> = (0..size).map(|_| (rng.gen(), counter.clone())).collect();
+            let mut keys: Vec<u32> = vec.iter().map(|pair| pair.0).collect();
+            let panic_after = rng.gen_range(0, size * 2);
+            let mut compares = 0;
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                sort(&mut vec, &mut |a, b| {
+                    compares += 1;
+                    if compares > panic_after {
+                        panic!("compare panics");
+                    }
+                    a.0.cmp(&b.0)
+                })
+            }));
+
+            if result.is_err() {
+                let mut remaining: Vec<u32> = vec.iter().map(|pair| pair.0).collect();
+                remaining.sort();
+                keys.sort();
+                assert_eq!(remaining, keys);
+            }
+            assert_eq!(Rc::strong_count(&counter), size + 1);
+            drop(vec);
+            assert_eq!(Rc::strong_count(&counter), 1);
+        }
+    }
+
+    #[test]
+    fn merge_sort_is_stable() {
+        let mut rng = thread_rng();
+        assert!(sorts_stably(&mut rng, merge_sort));
+        assert!(sorts_stably(&mut rng, |vec| merge_sort_by_key(vec, |indexed| indexed.key)));
+    }
+
+    #[test]
+    fn merge_sort_panics_without_losing_elements() {
+        sort_panicking(|slice, compare| merge_sort_by(slice, |a, b| compare(a, b)));
+    }
+
+    #[test]
+    fn bottom_up_merge_sort_some() {
+        let mut rng = thread_rng();
+        sort_some::<u32>(&mut rng, bottom_up_merge_sort);
+        sort_some_slices::<u32>(&mut rng, bottom_up_merge_sort);
+        sort_some_by(&mut rng,
+                     |slice| bottom_up_merge_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| bottom_up_merge_sort_by_key(slice, |pair| pair.1));
+        assert!(sorts_stably(&mut rng, bottom_up_merge_sort));
+        sort_panicking(|slice, compare| bottom_up_merge_sort_by(slice, |a, b| compare(a, b)));
+    }
+
+    #[test]
+    fn natural_merge_sort_some() {
+        let mut rng = thread_rng();
+        sort_some::<u32>(&mut rng, natural_merge_sort);
+        sort_some_slices::<u32>(&mut rng, natural_merge_sort);
+        sort_some_by(&mut rng,
+                     |slice| natural_merge_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| natural_merge_sort_by_key(slice, |pair| pair.1));
+        assert!(sorts_stably(&mut rng, natural_merge_sort));
+        sort_panicking(|slice, compare| natural_merge_sort_by(slice, |a, b| compare(a, b)));
+    }
+
+    #[test]
+    fn insertion_merge_sort_some() {
+        let mut rng = thread_rng();
+        sort_some::<u32>(&mut rng, |vec| insertion_merge_sort(vec, 4));
+        sort_some_slices::<u32>(&mut rng, |slice| insertion_merge_sort(slice, 16));
+        sort_some_by(&mut rng,
+                     |slice| insertion_merge_sort_by(slice, 3, |a, b| b.cmp(a)),
+                     |slice| insertion_merge_sort_by_key(slice, 8, |pair| pair.1));
+        for run_size in 1..20 {
+            assert!(sorts_stably(&mut rng, |vec| insertion_merge_sort(vec, run_size)));
+        }
+        sort_panicking(|slice, compare| insertion_merge_sort_by(slice, 5, |a, b| compare(a, b)));
+    }
+
+    #[test]
+    #[should_panic(expected = "the run size must not be 0")]
+    fn insertion_merge_sort_without_run_size() {
+        insertion_merge_sort(&mut vec![2, 1], 0);
+    }
+
+    #[test]
+    fn natural_merge_sort_runs() {
+        // ascending, strictly descending, and runs with equal elements
+        let mut vec = vec![1, 2, 2, 5, 9, 8, 7, 3, 3, 3, 4, 0];
+        natural_merge_sort(&mut vec);
+        assert_eq!(vec, vec![0, 1, 2, 2, 3, 3, 3, 4, 5, 7, 8, 9]);
+
+        let mut vec: Vec<u32> = (0..100).rev().collect();
+        natural_merge_sort(&mut vec);
+        assert_eq!(vec, (0..100).collect::<Vec<u32>>());
+
+        // equal keys in a descending run keep their order
+        let mut vec = vec![(3, 0), (2, 1), (2, 2), (1, 3)];
+        natural_merge_sort_by_key(&mut vec, |pair| pair.0);
+        assert_eq!(vec, vec![(1, 3), (2, 1), (2, 2), (3, 0)]);
+    }
+}
@@ -0,0 +1,373 @@
+//! A min-max heap, a double-ended priority queue by Atkinson et al.: a complete binary tree, like
+//! the binary heap of [`max_pq`], whose levels alternate between min levels and max levels. Each
+//! node on a min level, like the root, is not greater than any node below it, and each node on a
+//! max level is not less than any node below it.
+//!
+//! So the smallest item is at the root, and the largest one is one of its children. Both can be
+//! removed in logarithmic time, by sinking the item that takes their place through the levels of
+//! their kind, two levels at a time.
+//!
+//! [`max_pq`]: ../max_pq/index.html
+//!
+//! # Example
+//! A bounded collection, that keeps the largest items it has seen:
+//! ```
+//! use algorithms::min_max_heap::MinMaxHeap;
+//!
+//! let mut largest = MinMaxHeap::new();
+//! for item in vec![5, 1, 9, 3, 7, 8, 2] {
+//!     largest.push(item);
+//!     if largest.len() > 3 {
+//!         largest.pop_min();
+//!     }
+//! }
+//! assert_eq!(largest.peek_min(), Some(&7));
+//! assert_eq!(largest.pop_max(), Some(9));
+//! assert_eq!(largest.pop_max(), Some(8));
+//! ```
+
+use std::iter::FromIterator;
+use ::max_pq::{children, parent, MaxPQ, MinPQ};
+
+/// a min-max heap, with the smallest item at the root, and the largest one at one of its children
+#[derive(Clone, Debug)]
+pub struct MinMaxHeap<T> {
+    vec: Vec<T>,
+}
+
+// whether the index is on a min level, an even level of the tree
+fn is_on_min_level(k: usize) -> bool {
+    (k + 1).ilog2().is_multiple_of(2)
+}
+
+impl<T> MinMaxHeap<T>
+    where T: PartialOrd {
+    pub fn new() -> MinMaxHeap<T> {
+        MinMaxHeap { vec: Vec::new() }
+    }
+
+    /// makes a heap of the items of vec, by trickling down all nodes with children, in linear time
+    pub fn from_vec(vec: Vec<T>) -> MinMaxHeap<T> {
+        let mut heap = MinMaxHeap { vec };
+        let size = heap.vec.len();
+        if size > 1 {
+            for k in (0..=parent(size - 1)).rev() {
+                heap.trickle_down(k);
+            }
+        }
+        heap
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// the smallest item, if the heap is not empty
+    pub fn peek_min(&self) -> Option<&T> {
+        self.vec.first()
+    }
+
+    /// the largest item, if the heap is not empty
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|k| &self.vec[k])
+    }
+
+    /// inserts the item, and bubbles it up through the levels of its kind
+    pub fn push(&mut self, item: T) {
+        self.vec.push(item);
+        let k = self.vec.len() - 1;
+        self.bubble_up(k);
+    }
+
+    /// removes the smallest item, if the heap is not empty
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.vec.is_empty() { None } else { Some(self.remove(0)) }
+    }
+
+    /// removes the largest item, if the heap is not empty
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.max_index().map(|k| self.remove(k))
+    }
+
+    /// the items in the order of the heap
+    pub fn into_vec(self) -> Vec<T> {
+        self.vec
+    }
+
+    // the index of the largest item: the root, or the larger one of its children
+    fn max_index(&self) -> Option<usize> {
+        match self.vec.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => Some(if self.vec[1] < self.vec[2] { 2 } else { 1 }),
+        }
+    }
+
+    // whether a belongs above b on a min level, or a max level
+    fn before(&self, a: usize, b: usize, min: bool) -> bool {
+        if min { self.vec[a] < self.vec[b] } else { self.vec[b] < self.vec[a] }
+    }
+
+    // replaces the item at index k with the last item, and trickles that one down
+    fn remove(&mut self, k: usize) -> T {
+        let last = self.vec.len() - 1;
+        self.vec.swap(k, last);
+        let item = self.vec.pop().unwrap();
+        if k < last {
+            self.trickle_down(k);
+        }
+        item
+    }
+
+    fn bubble_up(&mut self, k: usize) {
+        if k == 0 {
+            return;
+        }
+        let p = parent(k);
+        let min = is_on_min_level(k);
+        if self.before(p, k, !min) {
+            // the parent belongs above the item, which stays on the levels of its own kind
+            self.bubble_up_levels(k, min);
+        } else {
+            // the item belongs above its parent, so it moves to the levels of the parent's kind
+            self.vec.swap(k, p);
+            self.bubble_up_levels(p, !min);
+        }
+    }
+
+    // swims the item at index k up through the levels of its kind, from grandparent to grandparent
+    fn bubble_up_levels(&mut self, k: usize, min: bool) {
+        let mut i = k;
+        while i > 2 {
+            let grandparent = parent(parent(i));
+            if !self.before(i, grandparent, min) {
+                return;
+            }
+            self.vec.swap(i, grandparent);
+            i = grandparent;
+        }
+    }
+
+    // sinks the item at index k down through the levels of its kind, from grandchild to grandchild
+    fn trickle_down(&mut self, k: usize) {
+        let min = is_on_min_level(k);
+        let size = self.vec.len();
+        let mut i = k;
+        loop {
+            let (first_child, last_child) = children(i);
+            if first_child >= size {
+                return;
+            }
+            // the index of the first item, in the order of the level, among the children and
+            // grandchildren
+            let (first_grandchild, _) = children(first_child);
+            let (_, last_grandchild) = children(last_child);
+            let mut m = first_child;
+            for j in (first_child + 1..=last_child).chain(first_grandchild..=last_grandchild) {
+                if j < size && self.before(j, m, min) {
+                    m = j;
+                }
+            }
+
+            if !self.before(m, i, min) {
+                return;
+            }
+            self.vec.swap(m, i);
+            if m <= last_child {
+                return;
+            }
+            // the item from the level above, that moved to the grandchild, may belong above its
+            // new parent, which is on a level of the other kind
+            let p = parent(m);
+            if self.before(p, m, min) {
+                self.vec.swap(m, p);
+            }
+            i = m;
+        }
+    }
+}
+
+impl<T> Default for MinMaxHeap<T>
+    where T: PartialOrd {
+    fn default() -> MinMaxHeap<T> {
+        MinMaxHeap::new()
+    }
+}
+
+impl<T> FromIterator<T> for MinMaxHeap<T>
+    where T: PartialOrd {
+    fn from_iter<I>(iter: I) -> MinMaxHeap<T>
+        where I: IntoIterator<Item=T> {
+        MinMaxHeap::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<T> MaxPQ for MinMaxHeap<T>
+    where T: PartialOrd {
+    type Item = T;
+
+    fn heap_insert(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn del_max(&mut self) -> Option<T> {
+        self.pop_max()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    fn size(&self) -> usize {
+        self.vec.len()
+    }
+}
+
+impl<T> MinPQ for MinMaxHeap<T>
+    where T: PartialOrd {
+    type Item = T;
+
+    fn heap_insert(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn del_min(&mut self) -> Option<T> {
+        self.pop_min()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    fn size(&self) -> usize {
+        self.vec.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
+    use ::max_pq::{parent, MaxPQ, MinPQ};
+    use super::{is_on_min_level, MinMaxHeap};
+
+    /// whether each node is in order with all its ancestors: not less than those on min levels,
+    /// and not greater than those on max levels
+    fn is_min_max_heap<T>(heap: &MinMaxHeap<T>) -> bool
+        where T: PartialOrd {
+        (1..heap.vec.len()).all(|k| {
+            let mut ancestor = k;
+            while ancestor > 0 {
+                ancestor = parent(ancestor);
+                let ordered = if is_on_min_level(ancestor) {
+                    heap.vec[ancestor] <= heap.vec[k]
+                } else {
+                    heap.vec[ancestor] >= heap.vec[k]
+                };
+                if !ordered {
+                    return false;
+                }
+            }
+            true
+        })
+    }
+
+    #[test]
+    fn levels() {
+        let min_levels: Vec<bool> = (0..16).map(is_on_min_level).collect();
+        assert_eq!(min_levels, vec![true, false, false, true, true, true, true,
+                                    false, false, false, false, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn operations_like_a_sorted_vec() {
+        let mut rng = thread_rng();
+        let mut heap = MinMaxHeap::new();
+        let mut oracle: Vec<u32> = vec![];
+        for _ in 0..5000 {
+            match rng.gen_range(0, 4) {
+                0 => assert_eq!(heap.pop_max(), oracle.pop()),
+                1 => assert_eq!(heap.pop_min(), if oracle.is_empty() { None } else { Some(oracle.remove(0)) }),
+                _ => {
+                    let item = rng.gen_range(0, 100);
+                    heap.push(item);
+                    let index = oracle.iter().position(|x| *x > item).unwrap_or(oracle.len());
+                    oracle.insert(index, item);
+                }
+            }
+            assert!(is_min_max_heap(&heap));
+            assert_eq!(heap.peek_min(), oracle.first());
+            assert_eq!(heap.peek_max(), oracle.last());
+            assert_eq!(heap.len(), oracle.len());
+        }
+    }
+
+    #[test]
+    fn from_iter() {
+        let mut rng = thread_rng();
+        for size in (0..20).map(|x| x * x) {
+            let mut sorted: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+            let mut heap: MinMaxHeap<u32> = sorted.iter().cloned().collect();
+            assert!(is_min_max_heap(&heap));
+
+            sorted.sort();
+            let mut lo = 0;
+            let mut hi = sorted.len();
+            while lo < hi {
+                if rng.gen() {
+                    assert_eq!(heap.pop_min(), Some(sorted[lo]));
+                    lo += 1;
+                } else {
+                    hi -= 1;
+                    assert_eq!(heap.pop_max(), Some(sorted[hi]));
+                }
+                assert!(is_min_max_heap(&heap));
+            }
+            assert!(heap.is_empty());
+            assert_eq!(heap.pop_min(), None);
+            assert_eq!(heap.pop_max(), None);
+        }
+    }
+
+    #[test]
+    fn sliding_window() {
+        // the minimum and maximum of each window of 4 consecutive items, kept with a min-max heap of
+        // (item, index) pairs, whose items that left the window are removed lazily
+        let mut rng = thread_rng();
+        let items: Vec<u32> = (0..200).map(|_| rng.gen_range(0, 1000)).collect();
+        let mut heap = MinMaxHeap::new();
+        for (i, item) in items.iter().enumerate() {
+            heap.push((*item, i));
+            if i < 3 {
+                continue;
+            }
+            while heap.peek_min().unwrap().1 + 4 <= i {
+                heap.pop_min();
+            }
+            while heap.peek_max().unwrap().1 + 4 <= i {
+                heap.pop_max();
+            }
+            let window = &items[i - 3..=i];
+            assert_eq!(heap.peek_min().unwrap().0, *window.iter().min().unwrap());
+            assert_eq!(heap.peek_max().unwrap().0, *window.iter().max().unwrap());
+        }
+    }
+
+    #[test]
+    fn priority_queues() {
+        let mut heap = MinMaxHeap::new();
+        for item in [4, 7, 1, 9] {
+            MaxPQ::heap_insert(&mut heap, item);
+        }
+        assert_eq!(heap.del_max(), Some(9));
+        assert_eq!(heap.del_min(), Some(1));
+        assert_eq!(MinPQ::size(&heap), 2);
+        assert!(!MaxPQ::is_empty(&heap));
+    }
+}
@@ -0,0 +1,286 @@
+//! A pairing heap: a heap-ordered tree, in which each node has any number of children. Two heaps
+//! are melded in constant time, by making the root with the smaller item a child of the other one.
+//!
+//! Inserting melds a heap of one node. Deleting the maximum melds the children of the root in two
+//! passes: it pairs them up from left to right, and then melds the pairs from right to left. That
+//! takes logarithmic time, amortized.
+//!
+//! # Example
+//! ```
+//! use algorithms::max_pq::{Meld, MaxPQ};
+//! use algorithms::pairing_heap::PairingHeap;
+//!
+//! let mut heap = PairingHeap::new();
+//! heap.heap_insert(3);
+//! heap.heap_insert(7);
+//!
+//! let mut other = PairingHeap::new();
+//! other.heap_insert(5);
+//! heap.meld(other);
+//!
+//! assert_eq!(heap.del_max(), Some(7));
+//! assert_eq!(heap.del_max(), Some(5));
+//! assert_eq!(heap.del_max(), Some(3));
+//! assert_eq!(heap.del_max(), None);
+//! ```
+
+use std::fmt;
+use ::max_pq::{MaxPQ, Meld};
+
+struct Node<T> {
+    item: T,
+    children: Vec<Node<T>>,
+}
+
+/// a pairing heap, with the largest item at the root
+pub struct PairingHeap<T> {
+    root: Option<Node<T>>,
+    size: usize,
+}
+
+// makes the root with the smaller item a child of the other one
+fn link<T>(mut a: Node<T>, mut b: Node<T>) -> Node<T>
+    where T: PartialOrd {
+    if a.item < b.item {
+        b.children.push(a);
+        b
+    } else {
+        a.children.push(b);
+        a
+    }
+}
+
+impl<T> PairingHeap<T>
+    where T: PartialOrd {
+    pub fn new() -> PairingHeap<T> {
+        PairingHeap { root: None, size: 0 }
+    }
+
+    /// the largest item, if the heap is not empty
+    pub fn max(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.item)
+    }
+
+    fn meld_root(&mut self, node: Node<T>) {
+        self.root = Some(match self.root.take() {
+            None => node,
+            Some(root) => link(root, node),
+        });
+    }
+}
+
+impl<T> Default for PairingHeap<T>
+    where T: PartialOrd {
+    fn default() -> PairingHeap<T> {
+        PairingHeap::new()
+    }
+}
+
+impl<T> Drop for PairingHeap<T> {
+    // drops the nodes one by one, as dropping the tree recursively could overflow the stack
+    fn drop(&mut self) {
+        let mut nodes: Vec<Node<T>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = nodes.pop() {
+            nodes.append(&mut node.children);
+        }
+    }
+}
+
+impl<T> Clone for PairingHeap<T>
+    where T: Clone {
+    // clones the nodes one by one, as cloning the tree recursively could overflow the stack. The path
+    // holds each node down to the one being cloned, with the clones of its children so far.
+    fn clone(&self) -> PairingHeap<T> {
+        fn leaf<T>(node: &Node<T>) -> Node<T>
+            where T: Clone {
+            Node { item: node.item.clone(), children: Vec::with_capacity(node.children.len()) }
+        }
+
+        let mut root = None;
+        let mut path: Vec<(&Node<T>, Node<T>)> = self.root.iter().map(|node| (node, leaf(node))).collect();
+        while let Some((node, clone)) = path.pop() {
+            if clone.children.len() < node.children.len() {
+                let child = &node.children[clone.children.len()];
+                path.push((node, clone));
+                path.push((child, leaf(child)));
+            } else {
+                match path.last_mut() {
+                    Some(&mut (_, ref mut parent)) => parent.children.push(clone),
+                    None => root = Some(clone),
+                }
+            }
+        }
+        PairingHeap { root, size: self.size }
+    }
+}
+
+impl<T> fmt::Debug for PairingHeap<T>
+    where T: fmt::Debug {
+    /// the items in preorder, from the largest one at the root on
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut nodes: Vec<&Node<T>> = self.root.iter().collect();
+        while let Some(node) = nodes.pop() {
+            list.entry(&node.item);
+            nodes.extend(node.children.iter().rev());
+        }
+        list.finish()
+    }
+}
+
+impl<T> Meld for PairingHeap<T>
+    where T: PartialOrd {
+    /// melds the other heap in constant time
+    fn meld(&mut self, mut other: PairingHeap<T>) {
+        if let Some(root) = other.root.take() {
+            self.meld_root(root);
+        }
+        self.size += other.size;
+    }
+}
+
+impl<T> MaxPQ for PairingHeap<T>
+    where T: PartialOrd {
+    type Item = T;
+
+    fn heap_insert(&mut self, item: T) {
+        self.meld_root(Node { item, children: Vec::new() });
+        self.size += 1;
+    }
+
+    fn del_max(&mut self) -> Option<T> {
+        self.root.take().map(|root| {
+            self.size -= 1;
+            let Node { item, children } = root;
+
+            let mut pairs = Vec::with_capacity(children.len().div_ceil(2));
+            let mut children = children.into_iter();
+            while let Some(a) = children.next() {
+                pairs.push(match children.next() {
+                    Some(b) => link(a, b),
+                    None => a,
+                });
+            }
+            while let Some(pair) = pairs.pop() {
+                self.meld_root(pair);
+            }
+
+            item
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
+    use ::max_pq::{MaxPQ, Meld};
+    use super::{Node, PairingHeap};
+
+    fn is_heap_ordered<T>(node: &Node<T>) -> bool
+        where T: PartialOrd {
+        node.children.iter().all(|child| node.item >= child.item && is_heap_ordered(child))
+    }
+
+    fn count<T>(node: &Node<T>) -> usize {
+        1 + node.children.iter().map(count).sum::<usize>()
+    }
+
+    #[test]
+    fn insert_and_delete_like_a_sorted_vec() {
+        let mut rng = thread_rng();
+        let mut heap = PairingHeap::new();
+        let mut oracle: Vec<u32> = vec![];
+        for _ in 0..1000 {
+            if rng.gen_range(0, 3) == 0 {
+                assert_eq!(heap.del_max(), oracle.pop());
+            } else {
+                let item = rng.gen_range(0, 100);
+                heap.heap_insert(item);
+                let index = oracle.iter().position(|x| *x > item).unwrap_or(oracle.len());
+                oracle.insert(index, item);
+            }
+            assert!(heap.root.as_ref().is_none_or(is_heap_ordered));
+            assert_eq!(heap.root.as_ref().map_or(0, count), oracle.len());
+            assert_eq!(heap.max(), oracle.last());
+            assert_eq!(heap.size(), oracle.len());
+        }
+    }
+
+    #[test]
+    fn meld_some() {
+        let mut rng = thread_rng();
+        for size in (0..20).map(|x| x * x) {
+            let mut heap = PairingHeap::new();
+            let mut other = PairingHeap::new();
+            let mut items: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+            for (i, item) in items.iter().enumerate() {
+                if i % 3 == 0 { heap.heap_insert(*item) } else { other.heap_insert(*item) }
+            }
+            heap.meld(other);
+            assert_eq!(heap.size(), size);
+
+            items.sort();
+            while let Some(max) = heap.del_max() {
+                assert_eq!(Some(max), items.pop());
+            }
+            assert!(items.is_empty());
+            assert!(heap.is_empty());
+        }
+    }
+
+    #[test]
+    fn drop_a_deep_heap() {
+        // ascending inserts make each root a child of the next one, a path of 100000 nodes
+        let mut heap = PairingHeap::new();
+        for item in 0..100000 {
+            heap.heap_insert(item);
+        }
+        assert_eq!(heap.max(), Some(&99999));
+    }
+
+    #[test]
+    fn clone_a_deep_heap() {
+        let mut heap = PairingHeap::new();
+        for item in 0..100000 {
+            heap.heap_insert(item);
+        }
+        // ascending inserts make a path of 100000 nodes, like in drop_a_deep_heap
+        let mut clone = heap.clone();
+        assert_eq!(format!("{:?}", clone), format!("{:?}", heap));
+        assert_eq!(clone.del_max(), Some(99999));
+        assert_eq!(clone.del_max(), Some(99998));
+        assert_eq!(heap.max(), Some(&99999));
+    }
+
+    #[test]
+    fn clone_and_debug() {
+        let mut heap: PairingHeap<u32> = PairingHeap::new();
+        assert_eq!(format!("{:?}", heap.clone()), "[]");
+        for item in [3, 1, 4, 1, 5, 9, 2, 6] {
+            heap.heap_insert(item);
+        }
+        heap.del_max();
+
+        let mut clone = heap.clone();
+        assert_eq!(format!("{:?}", clone), format!("{:?}", heap));
+        assert_eq!(clone.size(), heap.size());
+        assert!(format!("{:?}", heap).starts_with("[6, "));
+        let mut items = vec![];
+        while let Some(item) = clone.del_max() {
+            assert_eq!(heap.del_max(), Some(item));
+            items.push(item);
+        }
+        assert_eq!(items, vec![6, 5, 4, 3, 2, 1, 1]);
+        assert!(heap.is_empty());
+    }
+}
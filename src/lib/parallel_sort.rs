@@ -0,0 +1,365 @@
+//! Parallel versions of merge sort and quick sort, for slices. They sort subproblems of
+//! [`THRESHOLD`] elements or more on threads of their own, as long as there are cores left to
+//! run them on, and sort smaller subproblems sequentially.
+//!
+//! The results are identical to those of the sequential versions: [`parallel_merge_sort`] is
+//! stable like [`merge_sort`], and [`parallel_quick_sort`] partitions exactly like [`intro_sort`].
+//!
+//! [`THRESHOLD`]: constant.THRESHOLD.html
+//! [`parallel_merge_sort`]: fn.parallel_merge_sort.html
+//! [`parallel_quick_sort`]: fn.parallel_quick_sort.html
+//! [`merge_sort`]: ../merge_sort/fn.merge_sort.html
+//! [`intro_sort`]: ../quick_sort/fn.intro_sort.html
+//!
+//! # Example
+//! ```
+//! use algorithms::parallel_sort::parallel_merge_sort;
+//!
+//! let mut vec: Vec<u32> = (0..100_000).rev().collect();
+//! parallel_merge_sort(&mut vec);
+//! assert_eq!(vec, (0..100_000).collect::<Vec<u32>>());
+//! ```
+
+use std::cmp::Ordering;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::slice;
+use std::thread;
+use ::merge_sort::merge_sort_by_less;
+use ::quick_sort::{choose_pivot, intro_sort_range, lg, partition_at_equal_keys};
+
+/// subproblems with fewer elements are not split between threads
+pub const THRESHOLD: usize = 1 << 13;
+
+/// Sorts a slice like [`merge_sort`], sorting the halves and merging them in parallel.
+///
+/// Large merges are split in two by the middle element of the larger half, and the position that
+/// a binary search finds for it in the other half. The two smaller merges then run in parallel.
+///
+/// [`merge_sort`]: ../merge_sort/fn.merge_sort.html
+pub fn parallel_merge_sort<Item>(vec: &mut [Item])
+    where Item: PartialOrd + Send + Sync {
+    parallel_merge_sort_by_less(vec, &|a: &Item, b: &Item| a < b, available_threads(vec.len()));
+}
+
+/// sorts like [`parallel_merge_sort`], in the order defined by the compare function
+///
+/// [`parallel_merge_sort`]: fn.parallel_merge_sort.html
+pub fn parallel_merge_sort_by<Item, F>(vec: &mut [Item], compare: F)
+    where Item: Send + Sync,
+          F: Fn(&Item, &Item) -> Ordering + Sync {
+    parallel_merge_sort_by_less(vec, &|a: &Item, b: &Item| compare(a, b) == Ordering::Less, available_threads(vec.len()));
+}
+
+/// sorts like [`parallel_merge_sort`], in the order of the keys that the key function extracts
+///
+/// [`parallel_merge_sort`]: fn.parallel_merge_sort.html
+pub fn parallel_merge_sort_by_key<Item, K, F>(vec: &mut [Item], key: F)
+    where Item: Send + Sync,
+          K: Ord,
+          F: Fn(&Item) -> K + Sync {
+    parallel_merge_sort_by_less(vec, &|a: &Item, b: &Item| key(a) < key(b), available_threads(vec.len()));
+}
+
+/// Sorts a slice like [`intro_sort`], sorting the two partitions in parallel.
+///
+/// [`intro_sort`]: ../quick_sort/fn.intro_sort.html
+pub fn parallel_quick_sort<Item>(vec: &mut [Item])
+    where Item: PartialOrd + Send + Sync {
+    parallel_quick_sort_by_less(vec, &|a: &Item, b: &Item| a < b, available_threads(vec.len()));
+}
+
+/// sorts like [`parallel_quick_sort`], in the order defined by the compare function
+///
+/// [`parallel_quick_sort`]: fn.parallel_quick_sort.html
+pub fn parallel_quick_sort_by<Item, F>(vec: &mut [Item], compare: F)
+    where Item: Send + Sync,
+          F: Fn(&Item, &Item) -> Ordering + Sync {
+    parallel_quick_sort_by_less(vec, &|a: &Item, b: &Item| compare(a, b) == Ordering::Less, available_threads(vec.len()));
+}
+
+/// sorts like [`parallel_quick_sort`], in the order of the keys that the key function extracts
+///
+/// [`parallel_quick_sort`]: fn.parallel_quick_sort.html
+pub fn parallel_quick_sort_by_key<Item, K, F>(vec: &mut [Item], key: F)
+    where Item: Send + Sync,
+          K: Ord,
+          F: Fn(&Item) -> K + Sync {
+    parallel_quick_sort_by_less(vec, &|a: &Item, b: &Item| key(a) < key(b), available_threads(vec.len()));
+}
+
+/// the number of threads that can run in parallel, for sorting size elements. Finding the number
+/// of cores takes a system call, so small sizes skip it.
+fn available_threads(size: usize) -> usize {
+    if size < THRESHOLD {
+        1
+    } else {
+        thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+    }
+}
+
+/// runs both functions, on a thread of its own each, if there is more than one thread to run them on
+fn join<A, B>(threads: usize, a: A, b: B)
+    where A: FnOnce() + Send,
+          B: FnOnce() + Send {
+    if threads > 1 {
+        thread::scope(|scope| {
+            scope.spawn(a);
+            b();
+        });
+    } else {
+        a();
+        b();
+    }
+}
+
+fn parallel_merge_sort_by_less<Item, F>(vec: &mut [Item], less: &F, threads: usize)
+    where Item: Send + Sync,
+          F: Fn(&Item, &Item) -> bool + Sync {
+    let size = vec.len();
+    if threads <= 1 || size < THRESHOLD {
+        merge_sort_by_less(vec, &mut |a: &Item, b: &Item| less(a, b));
+        return;
+    }
+
+    let mid = size / 2;
+    {
+        let (left, right) = vec.split_at_mut(mid);
+        join(threads,
+             || parallel_merge_sort_by_less(left, less, threads / 2),
+             || parallel_merge_sort_by_less(right, less, threads - threads / 2));
+    }
+
+    // the halves are in order already
+    if !less(&vec[mid], &vec[mid - 1]) {
+        return;
+    }
+
+    // the auxilary array holds copies of the elements, while they are merged back into vec. Its
+    // length stays 0, so that it never drops them.
+    let mut aux_vec: Vec<Item> = Vec::with_capacity(size);
+    let aux_ptr = aux_vec.as_mut_ptr();
+    let vec_ptr = vec.as_mut_ptr();
+    unsafe {
+        ptr::copy_nonoverlapping(vec_ptr, aux_ptr, size);
+        let guard = Restore { src: aux_ptr, dst: vec_ptr, size };
+        let aux = slice::from_raw_parts(aux_ptr as *const Item, size);
+        let dst = slice::from_raw_parts_mut(vec_ptr as *mut MaybeUninit<Item>, size);
+        parallel_merge(&aux[..mid], &aux[mid..], dst, less, threads);
+        // every element was moved back into vec
+        guard.disarm();
+    }
+}
+
+/// Restores the elements of dst from src, when dropped. If a compare panics while merging, vec
+/// may hold some elements twice, and others not at all; restoring the copies before the merge
+/// makes sure, that each of them is dropped exactly once.
+struct Restore<Item> {
+    src: *const Item,
+    dst: *mut Item,
+    size: usize,
+}
+
+impl<Item> Restore<Item> {
+    fn disarm(self) {
+        ::std::mem::forget(self);
+    }
+}
+
+impl<Item> Drop for Restore<Item> {
+    fn drop(&mut self) {
+        unsafe { ptr::copy_nonoverlapping(self.src, self.dst, self.size) };
+    }
+}
+
+/// Merges the sorted slices left and right into dst, which must be as long as both together.
+/// The elements are moved bitwise, so the caller must not drop those of left and right.
+/// Equal elements are taken from left first, so that the merge is stable.
+unsafe fn parallel_merge<Item, F>(left: &[Item], right: &[Item], dst: &mut [MaybeUninit<Item>], less: &F, threads: usize)
+    where Item: Send + Sync,
+          F: Fn(&Item, &Item) -> bool + Sync {
+    if threads <= 1 || dst.len() < THRESHOLD || left.is_empty() || right.is_empty() {
+        merge(left, right, dst, less);
+        return;
+    }
+
+    // split by the middle element of the longer slice: the elements of the other slice, that go
+    // before it, are found by binary search
+    let (left_mid, right_mid) = if left.len() >= right.len() {
+        let left_mid = left.len() / 2;
+        // elements of right, that are equal to the split element, go after it
+        (left_mid, right.partition_point(|item| less(item, &left[left_mid])))
+    } else {
+        let right_mid = right.len() / 2;
+        // elements of left, that are equal to the split element, go before it
+        (left.partition_point(|item| !less(&right[right_mid], item)), right_mid)
+    };
+    let (dst_lo, dst_hi) = dst.split_at_mut(left_mid + right_mid);
+    let (left_lo, left_hi) = left.split_at(left_mid);
+    let (right_lo, right_hi) = right.split_at(right_mid);
+    join(threads,
+         || parallel_merge(left_lo, right_lo, dst_lo, less, threads / 2),
+         || parallel_merge(left_hi, right_hi, dst_hi, less, threads - threads / 2));
+}
+
+/// merges sequentially, like [`parallel_merge`]
+///
+/// [`parallel_merge`]: fn.parallel_merge.html
+unsafe fn merge<Item, F>(left: &[Item], right: &[Item], dst: &mut [MaybeUninit<Item>], less: &F)
+    where F: Fn(&Item, &Item) -> bool {
+    let mut i = 0;
+    let mut j = 0;
+    for slot in dst.iter_mut() {
+        if j < right.len() && (i == left.len() || less(&right[j], &left[i])) {
+            slot.write(ptr::read(&right[j]));
+            j += 1;
+        } else {
+            slot.write(ptr::read(&left[i]));
+            i += 1;
+        }
+    }
+}
+
+fn parallel_quick_sort_by_less<Item, F>(vec: &mut [Item], less: &F, threads: usize)
+    where Item: Send + Sync,
+          F: Fn(&Item, &Item) -> bool + Sync {
+    let size = vec.len();
+    parallel_intro_sort(vec, 2 * lg(size), less, threads);
+}
+
+/// sorts like [`intro_sort_range`] does, with the same partitions, but in parallel
+///
+/// [`intro_sort_range`]: ../quick_sort/fn.intro_sort_range.html
+fn parallel_intro_sort<Item, F>(vec: &mut [Item], depth: usize, less: &F, threads: usize)
+    where Item: Send + Sync,
+          F: Fn(&Item, &Item) -> bool + Sync {
+    let size = vec.len();
+    if threads <= 1 || size < THRESHOLD || depth == 0 {
+        intro_sort_range(vec, 0, size, depth, &mut |a: &Item, b: &Item| less(a, b));
+        return;
+    }
+
+    let pivot = choose_pivot(vec, 0, size, &mut |a: &Item, b: &Item| less(a, b));
+    vec.swap(0, pivot);
+    let k = partition_at_equal_keys(vec, 0, size, &mut |a: &Item, b: &Item| less(a, b));
+
+    let (lo, hi) = vec.split_at_mut(k);
+    join(threads,
+         || parallel_intro_sort(lo, depth - 1, less, threads / 2),
+         || parallel_intro_sort(&mut hi[1..], depth - 1, less, threads - threads / 2));
+}
+
+#[cfg(test)]
+mod test {
+    extern crate rand;
+
+    use std::cmp::Ordering;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::SeqCst;
+    use self::rand::{thread_rng, Rng};
+    use super::{parallel_merge_sort, parallel_merge_sort_by, parallel_merge_sort_by_key, parallel_merge_sort_by_less};
+    use super::{parallel_quick_sort, parallel_quick_sort_by, parallel_quick_sort_by_key, parallel_quick_sort_by_less};
+    use super::THRESHOLD;
+    use ::is_sorted::{sort_some, sort_some_by, sort_some_slices};
+    use ::merge_sort::merge_sort_by_key;
+    use ::quick_sort::intro_sort_by_key;
+
+    // enough elements to fork a few times
+    const SIZE: usize = 8 * THRESHOLD + 123;
+
+    fn pairs(keys: u32) -> Vec<(u32, usize)> {
+        let mut rng = thread_rng();
+        (0..SIZE).map(|i| (rng.gen_range(0, keys), i)).collect()
+    }
+
+    #[test]
+    fn parallel_sort_some() {
+        let mut rng = thread_rng();
+        sort_some::<u32>(&mut rng, |vec| parallel_merge_sort(vec));
+        sort_some::<u32>(&mut rng, |vec| parallel_quick_sort(vec));
+        sort_some_slices::<u32>(&mut rng, parallel_merge_sort);
+        sort_some_slices::<u32>(&mut rng, parallel_quick_sort);
+        sort_some_by(&mut rng,
+                     |slice| parallel_merge_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| parallel_merge_sort_by_key(slice, |pair| pair.1));
+        sort_some_by(&mut rng,
+                     |slice| parallel_quick_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| parallel_quick_sort_by_key(slice, |pair| pair.1));
+    }
+
+    // the results are identical to the sequential sorts, on any number of threads
+    #[test]
+    fn identical_to_sequential() {
+        for keys in &[3, 1000, u32::MAX] {
+            let vec = pairs(*keys);
+
+            let mut sequential = vec.clone();
+            merge_sort_by_key(&mut sequential, |pair| pair.0);
+            for threads in 1..6 {
+                let mut parallel = vec.clone();
+                parallel_merge_sort_by_less(&mut parallel, &|a: &(u32, usize), b: &(u32, usize)| a.0 < b.0, threads);
+                assert!(parallel == sequential);
+            }
+
+            let mut sequential = vec.clone();
+            intro_sort_by_key(&mut sequential, |pair| pair.0);
+            for threads in 1..6 {
+                let mut parallel = vec.clone();
+                parallel_quick_sort_by_less(&mut parallel, &|a: &(u32, usize), b: &(u32, usize)| a.0 < b.0, threads);
+                assert!(parallel == sequential);
+            }
+        }
+    }
+
+    #[test]
+    fn sorted_and_reversed() {
+        let sorted: Vec<usize> = (0..SIZE).collect();
+        for threads in &[2, 4] {
+            let mut vec = sorted.clone();
+            parallel_merge_sort_by_less(&mut vec, &|a: &usize, b: &usize| a < b, *threads);
+            assert!(vec == sorted);
+            let mut vec: Vec<usize> = sorted.iter().rev().cloned().collect();
+            parallel_merge_sort_by_less(&mut vec, &|a: &usize, b: &usize| a < b, *threads);
+            assert!(vec == sorted);
+            let mut vec: Vec<usize> = sorted.iter().rev().cloned().collect();
+            parallel_quick_sort_by_less(&mut vec, &|a: &usize, b: &usize| a < b, *threads);
+            assert!(vec == sorted);
+        }
+    }
+
+    #[test]
+    fn panics_without_losing_elements() {
+        let counter = Arc::new(());
+        let mut vec: Vec<(u32, Arc<()>)> = pairs(1000).into_iter().map(|pair| (pair.0, counter.clone())).collect();
+        let mut keys: Vec<u32> = vec.iter().map(|pair| pair.0).collect();
+
+        // the compares of the whole sort, of which the last merge takes about SIZE
+        let compares = AtomicUsize::new(0);
+        let mut sorted = keys.clone();
+        parallel_merge_sort_by_less(&mut sorted, &|a: &u32, b: &u32| {
+            compares.fetch_add(1, SeqCst);
+            a < b
+        }, 4);
+        let panic_after = compares.swap(0, SeqCst) - SIZE / 4;
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            parallel_merge_sort_by_less(&mut vec, &|a: &(u32, Arc<()>), b: &(u32, Arc<()>)| {
+                if compares.fetch_add(1, SeqCst) >= panic_after {
+                    panic!("compare panics");
+                }
+                a.0.cmp(&b.0) == Ordering::Less
+            }, 4)
+        }));
+
+        assert!(result.is_err());
+        let mut remaining: Vec<u32> = vec.iter().map(|pair| pair.0).collect();
+        remaining.sort();
+        keys.sort();
+        assert_eq!(remaining, keys);
+        assert_eq!(Arc::strong_count(&counter), SIZE + 1);
+        drop(vec);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+}
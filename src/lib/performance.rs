@@ -1,18 +1,32 @@
-//! Measure the growth rate of algorithms
+//! Measure the growth rate of algorithms, in terms of execution time and, with the
+//! [`TrackingAllocator`] installed, memory usage
+//!
+//! [`TrackingAllocator`]: struct.TrackingAllocator.html
+//!
 //! # Example
 //! ```
 //! use algorithms::performance;
 //! use algorithms::performance::example;
-//! let result = performance::observe::<Vec<i32>>(1000i64, Option::None);
+//! let report = performance::observe::<Vec<i32>>(1000i64, Option::None);
+//! println!("{}", report);
 //! ```
 
 extern crate rand;
-extern crate stopwatch;
 
-use self::rand::{ThreadRng, thread_rng};
-use self::stopwatch::Stopwatch;
+use self::rand::{Rng, ThreadRng, thread_rng};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::ops::RangeBounds;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::time::Instant;
 
-/// An algorithm that can be observed in terms of execution time
+/// An algorithm that can be observed in terms of execution time and memory usage
 pub trait PerformanceObservable {
 
     /// prepare a data-structure (with randomized content) of the given size
@@ -22,44 +36,776 @@ pub trait PerformanceObservable {
     fn run(&mut self, size: usize, rng: &mut ThreadRng);
 }
 
+/// the number of resamples drawn for a bootstrap confidence interval
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// the confidence level of the confidence intervals in reports
+const CONFIDENCE: f64 = 0.95;
+
+/// the widest confidence interval of the exponent of the power law, for which the samples are not
+/// considered too noisy
+const MAX_EXPONENT_UNCERTAINTY: f64 = 0.5;
+
+/// how [`observe_with`] takes its samples
+///
+/// [`observe_with`]: fn.observe_with.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObservationConfig {
+    /// stop doubling the size, when all the runs for the next size are expected to take longer than this
+    pub max_millis: i64,
+    /// stop doubling the size, when it reaches this size
+    pub max_size: Option<usize>,
+    /// the number of runs per size that are not measured, to warm up caches and the allocator
+    pub warm_up_runs: usize,
+    /// the number of measured runs per size
+    pub repetitions: usize,
+}
+
+impl ObservationConfig {
+    /// a configuration with one warm-up run and five repetitions per size
+    pub fn new(max_millis: i64, max_size: Option<usize>) -> ObservationConfig {
+        ObservationConfig {
+            max_millis,
+            max_size,
+            warm_up_runs: 1,
+            repetitions: 5,
+        }
+    }
+}
+
+/// the execution times of the repeated runs of an algorithm against data-structures of a
+/// certain size. All times are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+    pub size: usize,
+    /// the execution time of each run, in the order of the runs
+    pub elapsed_ns: Vec<u64>,
+}
+
+impl Sample {
+    pub fn median(&self) -> f64 {
+        median(self.elapsed_ns.iter().map(|ns| *ns as f64).collect())
+    }
+
+    pub fn mean(&self) -> f64 {
+        mean(&self.elapsed_ns.iter().map(|ns| *ns as f64).collect::<Vec<f64>>())
+    }
+
+    /// the sample standard deviation, or 0 if there are less than two runs
+    pub fn std_dev(&self) -> f64 {
+        let runs = self.elapsed_ns.len();
+        if runs < 2 {
+            0f64
+        } else {
+            let mean = self.mean();
+            let squares: f64 = self.elapsed_ns.iter().map(|ns| (*ns as f64 - mean) * (*ns as f64 - mean)).sum();
+            (squares / (runs - 1) as f64).sqrt()
+        }
+    }
+
+    /// a bootstrap confidence interval of the median, at the given confidence level (e.g. 0.95)
+    pub fn median_confidence_interval(&self, confidence: f64) -> (f64, f64) {
+        let mut rng = thread_rng();
+        let medians = (0..BOOTSTRAP_RESAMPLES).map(|_| self.resample(&mut rng).median()).collect();
+        percentile_interval(medians, confidence)
+    }
+
+    // a sample of the same size, with runs drawn from the runs of this sample, with replacement
+    fn resample(&self, rng: &mut ThreadRng) -> Sample {
+        let runs = self.elapsed_ns.len();
+        Sample {
+            size: self.size,
+            elapsed_ns: (0..runs).map(|_| self.elapsed_ns[rng.gen_range(0, runs)]).collect(),
+        }
+    }
+}
+
+/// a power law `a·N^b`, describing the execution time in nanoseconds for a size N
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PowerLaw {
+    pub a: f64,
+    pub b: f64,
+}
+
+impl PowerLaw {
+    /// the execution time in nanoseconds, that the power law predicts for the given size
+    pub fn predict(&self, size: usize) -> f64 {
+        self.a * (size as f64).powf(self.b)
+    }
+}
+
+/// the orders of growth presented in the course
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderOfGrowth {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl OrderOfGrowth {
+    /// all orders of growth, from the slowest to the fastest growing one
+    pub fn all() -> [OrderOfGrowth; 6] {
+        use self::OrderOfGrowth::*;
+        [Constant, Logarithmic, Linear, Linearithmic, Quadratic, Cubic]
+    }
+
+    /// the function of the size N that describes this order of growth
+    fn model(&self, size: f64) -> f64 {
+        use self::OrderOfGrowth::*;
+        match *self {
+            Constant => 1.0,
+            Logarithmic => size.log2(),
+            Linear => size,
+            Linearithmic => size * size.log2(),
+            Quadratic => size * size,
+            Cubic => size * size * size,
+        }
+    }
+}
+
+impl fmt::Display for OrderOfGrowth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::OrderOfGrowth::*;
+        let name = match *self {
+            Constant => "1",
+            Logarithmic => "log N",
+            Linear => "N",
+            Linearithmic => "N log N",
+            Quadratic => "N^2",
+            Cubic => "N^3",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// the samples taken by [`observe`], together with the models that can be fitted to them
+///
+/// [`observe`]: fn.observe.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObservationReport {
+    samples: Vec<Sample>,
+}
+
+impl ObservationReport {
+    pub fn new(samples: Vec<Sample>) -> ObservationReport {
+        ObservationReport { samples }
+    }
+
+    /// the samples, ordered by increasing size
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples
+    }
+
+    /// the ratio of the median execution time of the last size and the size before that,
+    /// or 0 if there are not enough samples with a measurable execution time
+    pub fn ratio(&self) -> f64 {
+        ratio(&self.samples)
+    }
+
+    /// fits a power law `a·N^b` to the median execution times, with a linear regression of `lg T`
+    /// against `lg N`.
+    ///
+    /// Samples with a median of 0 carry no information and are ignored. The other samples
+    /// are weighted by their median, as constant overheads and the resolution of the timer make
+    /// the short runs the least reliable ones.
+    /// Returns `None` if there are fewer than two samples of different sizes to fit against.
+    pub fn power_law(&self) -> Option<PowerLaw> {
+        fit_power_law(&self.log_points())
+    }
+
+    /// a bootstrap confidence interval of the exponent `b` of the [`power_law`], at the given
+    /// confidence level (e.g. 0.95). The runs of each sample are resampled, and the power law is
+    /// fitted to the medians of the resamples.
+    /// Returns `None` if there are fewer than two samples of different sizes to fit against.
+    ///
+    /// [`power_law`]: #method.power_law
+    pub fn exponent_confidence_interval(&self, confidence: f64) -> Option<(f64, f64)> {
+        self.power_law()?;
+
+        let mut rng = thread_rng();
+        let exponents = (0..BOOTSTRAP_RESAMPLES)
+            .filter_map(|_| {
+                let resamples = self.samples.iter().map(|s| s.resample(&mut rng)).collect();
+                ObservationReport::new(resamples).power_law()
+            })
+            .map(|power_law| power_law.b)
+            .collect();
+        Some(percentile_interval(exponents, confidence))
+    }
+
+    /// whether the samples are too noisy to draw conclusions about the order of growth from them.
+    /// This is the case if no power law can be fitted, or if the 95% confidence interval of its
+    /// exponent is wider than 0.5.
+    pub fn is_noisy(&self) -> bool {
+        match self.exponent_confidence_interval(CONFIDENCE) {
+            None => true,
+            Some((lo, hi)) => hi - lo > MAX_EXPONENT_UNCERTAINTY,
+        }
+    }
+
+    /// the order of growth that describes the samples best.
+    ///
+    /// For each order of growth `f(N)`, the constant `c` of the model `c·f(N)` is fitted to the
+    /// medians in log-space, and the order of growth with the smallest weighted squared error is
+    /// chosen. The samples are weighted as in [`power_law`].
+    /// Returns `None` if there are fewer than two samples of different sizes to fit against.
+    ///
+    /// [`power_law`]: #method.power_law
+    pub fn order_of_growth(&self) -> Option<OrderOfGrowth> {
+        // the power law fails exactly when there is nothing to distinguish the models by
+        self.power_law()?;
+
+        // lg N is 0 for N = 1, which only the constant model can describe
+        let points: Vec<(f64, f64, f64)> = self.log_points().into_iter().filter(|p| p.0 > 0f64).collect();
+
+        let error = |order: &OrderOfGrowth| {
+            let residuals: Vec<(f64, f64)> = points.iter()
+                .map(|p| (p.1 - order.model(p.0.exp2()).log2(), p.2))
+                .collect();
+            let total_weight: f64 = residuals.iter().map(|r| r.1).sum();
+            let lg_c = residuals.iter().map(|r| r.0 * r.1).sum::<f64>() / total_weight;
+            residuals.iter().map(|r| r.1 * (r.0 - lg_c) * (r.0 - lg_c)).sum::<f64>()
+        };
+
+        OrderOfGrowth::all().iter()
+            .map(|order| (*order, error(order)))
+            .min_by(|x, y| x.1.partial_cmp(&y.1).unwrap_or(Ordering::Equal))
+            .map(|best| best.0)
+    }
+
+    // the samples with a measurable median, as triples of lg N, lg T and weight
+    fn log_points(&self) -> Vec<(f64, f64, f64)> {
+        self.samples.iter()
+            .map(|s| (s.size, s.median()))
+            .filter(|&(_, median)| median > 0f64)
+            .map(|(size, median)| ((size as f64).log2(), median.log2(), median))
+            .collect()
+    }
+}
+
+impl fmt::Display for ObservationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for sample in &self.samples {
+            let (lo, hi) = sample.median_confidence_interval(CONFIDENCE);
+            writeln!(f, "Size: {}, median ns: {:.0} (95% CI {:.0} - {:.0}), mean ns: {:.0}, std dev ns: {:.0}",
+                     sample.size, sample.median(), lo, hi, sample.mean(), sample.std_dev())?;
+        }
+        writeln!(f, "Ratio: {}", self.ratio())?;
+        match (self.power_law(), self.exponent_confidence_interval(CONFIDENCE)) {
+            (Some(power_law), Some((lo, hi))) =>
+                writeln!(f, "Power law: {:e} * N^{:.3} (95% CI of exponent {:.3} - {:.3})", power_law.a, power_law.b, lo, hi)?,
+            _ => writeln!(f, "Power law: not enough samples")?,
+        }
+        match self.order_of_growth() {
+            Some(order) => write!(f, "Order of growth: {}", order),
+            None => write!(f, "Order of growth: not enough samples"),
+        }
+    }
+}
+
+/// a size, for which the median execution time regressed against a baseline
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Regression {
+    pub size: usize,
+    pub baseline_median: f64,
+    pub median: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Size: {}, median ns: {:.0}, baseline median ns: {:.0} ({:+.1}%)",
+               self.size, self.median, self.baseline_median, (self.median / self.baseline_median - 1f64) * 100f64)
+    }
+}
+
+impl ObservationReport {
+    /// writes the samples as CSV, with a header line and one line `size,elapsed_ns` per run
+    pub fn write_csv<W>(&self, writer: &mut W) -> io::Result<()>
+        where W: Write {
+        writeln!(writer, "size,elapsed_ns")?;
+        for sample in &self.samples {
+            for ns in &sample.elapsed_ns {
+                writeln!(writer, "{},{}", sample.size, ns)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// reads samples, as written by [`write_csv`]. Consecutive runs of the same size form a sample.
+    ///
+    /// [`write_csv`]: #method.write_csv
+    pub fn read_csv<R>(reader: R) -> io::Result<ObservationReport>
+        where R: BufRead {
+        let mut samples: Vec<Sample> = vec!();
+        for (number, line) in reader.lines().enumerate().skip(1) {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.trim().split(',').collect();
+            let parse_error = || io::Error::new(io::ErrorKind::InvalidData,
+                                                format!("line {}: expected size,elapsed_ns but found '{}'", number + 1, line));
+            if fields.len() != 2 {
+                return Err(parse_error());
+            }
+            let size: usize = fields[0].parse().map_err(|_| parse_error())?;
+            let ns: u64 = fields[1].parse().map_err(|_| parse_error())?;
+
+            match samples.last_mut() {
+                Some(ref mut sample) if sample.size == size => sample.elapsed_ns.push(ns),
+                _ => samples.push(Sample { size, elapsed_ns: vec![ns] }),
+            }
+        }
+        Ok(ObservationReport::new(samples))
+    }
+
+    /// writes the samples with their statistics, the power law and the order of growth as JSON
+    pub fn write_json<W>(&self, writer: &mut W) -> io::Result<()>
+        where W: Write {
+        writeln!(writer, "{{")?;
+        writeln!(writer, "  \"samples\": [")?;
+        for (i, sample) in self.samples.iter().enumerate() {
+            let runs: Vec<String> = sample.elapsed_ns.iter().map(|ns| ns.to_string()).collect();
+            writeln!(writer, "    {{\"size\": {}, \"elapsed_ns\": [{}], \"median_ns\": {}, \"mean_ns\": {}, \"std_dev_ns\": {}}}{}",
+                     sample.size, runs.join(", "), sample.median(), sample.mean(), sample.std_dev(),
+                     if i + 1 < self.samples.len() { "," } else { "" })?;
+        }
+        writeln!(writer, "  ],")?;
+        match self.power_law() {
+            Some(power_law) => writeln!(writer, "  \"power_law\": {{\"a\": {}, \"b\": {}}},", power_law.a, power_law.b)?,
+            None => writeln!(writer, "  \"power_law\": null,")?,
+        }
+        match self.order_of_growth() {
+            Some(order) => writeln!(writer, "  \"order_of_growth\": \"{}\"", order)?,
+            None => writeln!(writer, "  \"order_of_growth\": null")?,
+        }
+        writeln!(writer, "}}")
+    }
+
+    /// the sizes for which the median execution time exceeds the median of the baseline by more than
+    /// the threshold (e.g. 0.2 for 20%). To not flag mere noise, the 95% confidence intervals of the
+    /// two medians must not overlap either. Sizes that are missing in one of the reports are skipped.
+    pub fn regressions(&self, baseline: &ObservationReport, threshold: f64) -> Vec<Regression> {
+        self.samples.iter()
+            .filter_map(|sample| {
+                baseline.samples.iter()
+                    .find(|b| b.size == sample.size)
+                    .map(|b| (b, sample))
+            })
+            .filter(|&(b, sample)| sample.median() > b.median() * (1f64 + threshold))
+            .filter(|&(b, sample)| sample.median_confidence_interval(CONFIDENCE).0 > b.median_confidence_interval(CONFIDENCE).1)
+            .map(|(b, sample)| Regression { size: sample.size, baseline_median: b.median(), median: sample.median() })
+            .collect()
+    }
+}
+
+/// the environment variable that names the directory of the baselines used by [`check_baseline`]
+///
+/// [`check_baseline`]: fn.check_baseline.html
+pub const BASELINE_DIR_VAR: &str = "ALGORITHMS_BASELINE_DIR";
+
+/// compares a report against the baseline `<name>.csv` in the directory named by the environment
+/// variable `ALGORITHMS_BASELINE_DIR`, and returns the [`regressions`].
+/// If there is no baseline for the name yet, the report is stored as the baseline.
+/// If the environment variable is not set, there is nothing to compare against, and there are no
+/// regressions.
+///
+/// [`regressions`]: struct.ObservationReport.html#method.regressions
+pub fn check_baseline(name: &str, report: &ObservationReport, threshold: f64) -> io::Result<Vec<Regression>> {
+    let dir = match env::var_os(BASELINE_DIR_VAR) {
+        None => return Ok(vec!()),
+        Some(dir) => PathBuf::from(dir),
+    };
+    let path = dir.join(format!("{}.csv", name));
+
+    if path.exists() {
+        let baseline = ObservationReport::read_csv(BufReader::new(File::open(&path)?))?;
+        Ok(report.regressions(&baseline, threshold))
+    } else {
+        fs::create_dir_all(&dir)?;
+        report.write_csv(&mut File::create(&path)?)?;
+        Ok(vec!())
+    }
+}
+
+/// prints the report, asserts that it has no regressions against the baseline of the name (see
+/// [`check_baseline`], with a threshold of 25%), and that the exponent of its power law is in the
+/// range. The exponent is not checked, if the samples are too noisy.
+///
+/// [`check_baseline`]: fn.check_baseline.html
+pub fn check_exponent<R>(name: &str, report: &ObservationReport, range: R)
+    where R: RangeBounds<f64> + fmt::Debug {
+    println!("{}", report);
+    let regressions = check_baseline(name, report, 0.25).unwrap();
+    assert!(regressions.is_empty(), "regressions against the baseline: {:?}", regressions);
+    if report.is_noisy() {
+        println!("The samples are too noisy to check the exponent");
+        return;
+    }
+    let b = report.power_law().unwrap().b;
+    println!("Exponent {} = {}", name, b);
+
+    assert!(range.contains(&b), "the exponent {} of {} is out of the range {:?}", b, name, range);
+}
+
+/// the speed of b relative to a: the ratio of the median execution times of a and b, for the
+/// largest size both reports observed. Returns that size, and the ratio, or None if the reports
+/// have no size in common.
+pub fn relative_speed(a: &ObservationReport, b: &ObservationReport) -> Option<(usize, f64)> {
+    b.samples.iter()
+        .filter_map(|b| {
+            a.samples.iter()
+                .find(|a| a.size == b.size)
+                .map(|a| (b.size, a.median() / b.median()))
+        })
+        .max_by_key(|&(size, _)| size)
+}
+
+/// the memory usage of the current thread, as tracked by the [`TrackingAllocator`].
+/// The byte counts are the sizes requested from the allocator.
+///
+/// [`TrackingAllocator`]: struct.TrackingAllocator.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub allocations: usize,
+    pub reallocations: usize,
+    pub deallocations: usize,
+    /// the bytes that were allocated, and not deallocated yet. This is negative, if more memory was
+    /// deallocated than allocated.
+    pub live_bytes: isize,
+    /// the maximum of the live bytes
+    pub peak_bytes: isize,
+}
+
+impl fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "live bytes: {}, peak bytes: {}, allocations: {}, reallocations: {}, deallocations: {}",
+               self.live_bytes, self.peak_bytes, self.allocations, self.reallocations, self.deallocations)
+    }
+}
+
+impl MemoryUsage {
+    const ZERO: MemoryUsage = MemoryUsage {
+        allocations: 0,
+        reallocations: 0,
+        deallocations: 0,
+        live_bytes: 0,
+        peak_bytes: 0,
+    };
+
+    fn change_live_bytes(&mut self, bytes: isize) {
+        self.live_bytes += bytes;
+        self.peak_bytes = self.peak_bytes.max(self.live_bytes);
+    }
+}
+
+thread_local! {
+    static MEMORY_USAGE: Cell<MemoryUsage> = const { Cell::new(MemoryUsage::ZERO) };
+}
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+
+/// the number of calls to track that are running, in any thread. Allocations are only recorded
+/// while there are some, to keep the allocator cheap otherwise.
+static ACTIVE_TRACKS: AtomicUsize = AtomicUsize::new(0);
+
+fn record<F>(f: F)
+    where F: FnOnce(&mut MemoryUsage) {
+    if !TRACKING.load(AtomicOrdering::Relaxed) {
+        TRACKING.store(true, AtomicOrdering::Relaxed);
+    }
+    if ACTIVE_TRACKS.load(AtomicOrdering::Relaxed) == 0 {
+        return;
+    }
+    // the thread-local is gone while the thread shuts down; those allocations are not tracked
+    let _ = MEMORY_USAGE.try_with(|usage| {
+        let mut u = usage.get();
+        f(&mut u);
+        usage.set(u);
+    });
+}
+
+/// a global allocator, that tracks the memory usage of each thread, and delegates to the
+/// system allocator. Install it in a test crate, to measure memory with [`track`] and [`observe_memory`]:
+///
+/// ```
+/// use algorithms::performance::TrackingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+/// # fn main() {}
+/// ```
+///
+/// [`track`]: fn.track.html
+/// [`observe_memory`]: fn.observe_memory.html
+pub struct TrackingAllocator;
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(|u| {
+                u.allocations += 1;
+                u.change_live_bytes(layout.size() as isize);
+            });
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(|u| {
+                u.allocations += 1;
+                u.change_live_bytes(layout.size() as isize);
+            });
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(|u| {
+            u.deallocations += 1;
+            u.change_live_bytes(-(layout.size() as isize));
+        });
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(|u| {
+                u.reallocations += 1;
+                u.change_live_bytes(new_size as isize - layout.size() as isize);
+            });
+        }
+        new_ptr
+    }
+}
+
+/// whether the [`TrackingAllocator`] is installed as the global allocator (and has allocated already)
+///
+/// [`TrackingAllocator`]: struct.TrackingAllocator.html
+pub fn is_tracking() -> bool {
+    TRACKING.load(AtomicOrdering::Relaxed)
+}
+
+/// runs f and returns its result, together with the memory that the current thread used while
+/// running it. All counts are 0, unless the [`TrackingAllocator`] is installed.
+///
+/// [`TrackingAllocator`]: struct.TrackingAllocator.html
+pub fn track<F, R>(f: F) -> (R, MemoryUsage)
+    where F: FnOnce() -> R {
+    let outer = MEMORY_USAGE.with(|usage| usage.replace(MemoryUsage::ZERO));
+    ACTIVE_TRACKS.fetch_add(1, AtomicOrdering::SeqCst);
+    let result = f();
+    ACTIVE_TRACKS.fetch_sub(1, AtomicOrdering::SeqCst);
+    let inner = MEMORY_USAGE.with(|usage| usage.get());
+    // continue tracking for an enclosing call of track
+    MEMORY_USAGE.with(|usage| {
+        let mut u = outer;
+        u.allocations += inner.allocations;
+        u.reallocations += inner.reallocations;
+        u.deallocations += inner.deallocations;
+        u.peak_bytes = u.peak_bytes.max(u.live_bytes + inner.peak_bytes);
+        u.live_bytes += inner.live_bytes;
+        usage.set(u);
+    });
+    (result, inner)
+}
+
+/// the memory used for preparing a data-structure of a certain size, and for running an algorithm
+/// against it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MemorySample {
+    pub size: usize,
+    /// the live bytes are the size of the prepared data-structure
+    pub prepare: MemoryUsage,
+    pub run: MemoryUsage,
+}
+
+/// the result of [`observe_memory`]
+///
+/// [`observe_memory`]: fn.observe_memory.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryReport {
+    samples: Vec<MemorySample>,
+}
+
+impl MemoryReport {
+    pub fn new(samples: Vec<MemorySample>) -> MemoryReport {
+        MemoryReport { samples }
+    }
+
+    pub fn samples(&self) -> &[MemorySample] {
+        &self.samples
+    }
+
+    /// the power law `bytes = a * N^b`, fitted to the size of the prepared data-structures.
+    /// Like the power law of the execution times, the samples are weighted by their size in bytes.
+    /// Returns None if there are not enough samples with a positive size.
+    pub fn power_law(&self) -> Option<PowerLaw> {
+        let points: Vec<(f64, f64, f64)> = self.samples.iter()
+            .filter(|sample| sample.prepare.live_bytes > 0)
+            .map(|sample| (sample.size as f64, sample.prepare.live_bytes as f64))
+            .map(|(size, bytes)| (size.log2(), bytes.log2(), bytes))
+            .collect();
+        fit_power_law(&points)
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for sample in &self.samples {
+            writeln!(f, "Size: {}, prepare: {}", sample.size, sample.prepare)?;
+            writeln!(f, "Size: {}, run: {}", sample.size, sample.run)?;
+        }
+        match self.power_law() {
+            Some(power_law) => write!(f, "Memory power law: {:.3} * N^{:.3} bytes", power_law.a, power_law.b),
+            None => write!(f, "Memory power law: not enough samples"),
+        }
+    }
+}
+
+/// observes the execution time of an algorithm, like [`observe_with`] with the configuration
+/// [`ObservationConfig::new(max_millis, max_size)`].
+///
+/// [`observe_with`]: fn.observe_with.html
+/// [`ObservationConfig::new(max_millis, max_size)`]: struct.ObservationConfig.html#method.new
+pub fn observe<PO>(max_millis: i64, max_size: Option<usize>) -> ObservationReport
+    where PO: PerformanceObservable {
+    observe_with::<PO>(&ObservationConfig::new(max_millis, max_size))
+}
+
 /// observes the execution time of an algorithm, by doubling
-/// the size of the data-structure (of type PO) that the algorithm works on
-/// returns the ratio of the execution time of the last ran and the run
-/// before that
-pub fn observe<PO>(max_millis: i64, max_size: Option<usize>) -> f64
+/// the size of the data-structure (of type PO) that the algorithm works on,
+/// until the runs for the next size are expected to take longer than max_millis,
+/// or the size reaches max_size.
+/// For every size, the data-structure is prepared anew for each warm-up run and each repetition.
+/// returns a report of all the samples taken
+pub fn observe_with<PO>(config: &ObservationConfig) -> ObservationReport
     where PO: PerformanceObservable {
+    assert!(config.repetitions > 0);
+
     let mut rng = thread_rng();
-    let mut stopwatch = Stopwatch::new();
-    let mut elapsed_ms = 0i64;
+    let mut samples = vec!();
     let mut expected_ms = 0f64;
-    let mut ratio = 0f64;
     let mut size = 1usize;
 
-    while expected_ms <= max_millis as f64 && max_size.iter().all(|ms| *ms > size) {
-        let mut perf_obs: PO = PerformanceObservable::prepare(size, &mut rng);
+    while expected_ms <= config.max_millis as f64 && config.max_size.iter().all(|ms| *ms > size) {
+        for _ in 0..config.warm_up_runs {
+            let mut perf_obs: PO = PerformanceObservable::prepare(size, &mut rng);
+            perf_obs.run(size, &mut rng);
+        }
 
-        stopwatch.reset();
-        stopwatch.start();
+        let elapsed_ns = (0..config.repetitions)
+            .map(|_| {
+                let mut perf_obs: PO = PerformanceObservable::prepare(size, &mut rng);
 
-        perf_obs.run(size, &mut rng);
+                let start = Instant::now();
+                perf_obs.run(size, &mut rng);
+                start.elapsed().as_nanos() as u64
+            })
+            .collect();
 
-        let new_elapsed_ms = stopwatch.elapsed_ms();
+        samples.push(Sample { size, elapsed_ns });
 
-        println!("Size: {}, elapsed milliseconds: {}", size, new_elapsed_ms);
+        let runs = (config.warm_up_runs + config.repetitions) as f64;
+        expected_ms = samples[samples.len() - 1].median() * ratio(&samples) * runs / 1e6;
 
-        if elapsed_ms != 0 {
-            ratio = new_elapsed_ms as f64 / elapsed_ms as f64;
-            println!("Ratio: {}", ratio);
-        }
+        size *= 2;
+    }
+
+    ObservationReport::new(samples)
+}
+
+/// observes the memory usage of an algorithm and the data-structure it works on, by doubling
+/// the size of the data-structure (of type PO) until it reaches max_size.
+/// Requires the [`TrackingAllocator`] to be installed as the global allocator.
+///
+/// [`TrackingAllocator`]: struct.TrackingAllocator.html
+pub fn observe_memory<PO>(max_size: usize) -> MemoryReport
+    where PO: PerformanceObservable {
+    let mut rng = thread_rng();
+    assert!(is_tracking(), "the TrackingAllocator must be installed as the global allocator");
+
+    let mut samples = vec!();
+    let mut size = 1usize;
+
+    while size < max_size {
+        let (mut perf_obs, prepare) = track(|| -> PO { PerformanceObservable::prepare(size, &mut rng) });
+        let ((), run) = track(|| perf_obs.run(size, &mut rng));
+        samples.push(MemorySample { size, prepare, run });
+
+        size *= 2;
+    }
+
+    MemoryReport::new(samples)
+}
+
+// the ratio of the medians of the last two samples, or 0 if there are not enough samples
+fn ratio(samples: &[Sample]) -> f64 {
+    let size = samples.len();
+    if size < 2 || samples[size - 2].median() == 0f64 {
+        0f64
+    } else {
+        samples[size - 1].median() / samples[size - 2].median()
+    }
+}
 
-        elapsed_ms = new_elapsed_ms;
+// fits a power law with a weighted linear regression, to triples of lg N, lg T and weight
+fn fit_power_law(points: &[(f64, f64, f64)]) -> Option<PowerLaw> {
+    let total_weight: f64 = points.iter().map(|p| p.2).sum();
+    if total_weight == 0f64 {
+        return None;
+    }
+    let mean_x = points.iter().map(|p| p.0 * p.2).sum::<f64>() / total_weight;
+    let mean_y = points.iter().map(|p| p.1 * p.2).sum::<f64>() / total_weight;
+
+    let sxx: f64 = points.iter().map(|p| p.2 * (p.0 - mean_x) * (p.0 - mean_x)).sum();
+    let sxy: f64 = points.iter().map(|p| p.2 * (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    if sxx == 0f64 {
+        return None;
+    }
 
-        size = size * 2;
+    let b = sxy / sxx;
+    let a = (mean_y - b * mean_x).exp2();
+    Some(PowerLaw { a, b })
+}
 
-        expected_ms = elapsed_ms as f64 * ratio;
+fn median(mut values: Vec<f64>) -> f64 {
+    let size = values.len();
+    if size == 0 {
+        return 0f64;
     }
+    values.sort_by(|x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal));
+    if size % 2 == 1 {
+        values[size / 2]
+    } else {
+        (values[size / 2 - 1] + values[size / 2]) / 2f64
+    }
+}
 
-    ratio
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0f64
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+// the range between the percentiles that cut off (1 - confidence) / 2 of the values on each side
+fn percentile_interval(mut values: Vec<f64>, confidence: f64) -> (f64, f64) {
+    let size = values.len();
+    if size == 0 {
+        return (0f64, 0f64);
+    }
+    values.sort_by(|x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal));
+    let cut = (((1f64 - confidence) / 2f64 * size as f64) as usize).min((size - 1) / 2);
+    (values[cut], values[size - 1 - cut])
 }
 
 pub mod example {
@@ -80,3 +826,230 @@ pub mod example {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MemoryUsage, ObservationReport, OrderOfGrowth, Regression, Sample, TrackingAllocator, observe_memory, relative_speed, track};
+
+    #[global_allocator]
+    static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+    fn report_of(f: fn(f64) -> f64) -> ObservationReport {
+        let samples = (0..16)
+            .map(|i| 1usize << i)
+            .map(|size| Sample { size, elapsed_ns: vec![f(size as f64).round() as u64] })
+            .collect();
+        ObservationReport::new(samples)
+    }
+
+    #[test]
+    fn sample_statistics() {
+        let sample = Sample { size: 1, elapsed_ns: vec![4, 1, 3, 2] };
+        assert_eq!(sample.median(), 2.5);
+        assert_eq!(sample.mean(), 2.5);
+        assert!((sample.std_dev() - (5f64 / 3f64).sqrt()).abs() < 1e-9);
+
+        let sample = Sample { size: 1, elapsed_ns: vec![7, 1, 4] };
+        assert_eq!(sample.median(), 4.0);
+        assert_eq!(sample.std_dev(), 3.0);
+
+        let sample = Sample { size: 1, elapsed_ns: vec![7] };
+        assert_eq!(sample.median(), 7.0);
+        assert_eq!(sample.std_dev(), 0.0);
+    }
+
+    #[test]
+    fn median_confidence_interval() {
+        let sample = Sample { size: 1, elapsed_ns: vec![5, 5, 5] };
+        assert_eq!(sample.median_confidence_interval(0.95), (5.0, 5.0));
+
+        let sample = Sample { size: 1, elapsed_ns: vec![10, 11, 12, 13, 14, 15, 16, 17, 18] };
+        let (lo, hi) = sample.median_confidence_interval(0.95);
+        assert!((10.0..=14.0).contains(&lo));
+        assert!((14.0..=18.0).contains(&hi));
+    }
+
+    #[test]
+    fn ratio() {
+        let report = report_of(|n| n * n);
+        assert_eq!(report.ratio(), 4.0);
+
+        assert_eq!(ObservationReport::new(vec!()).ratio(), 0.0);
+        assert_eq!(ObservationReport::new(vec!(Sample { size: 1, elapsed_ns: vec![0] }, Sample { size: 2, elapsed_ns: vec![3] })).ratio(), 0.0);
+    }
+
+    #[test]
+    fn power_law_exact() {
+        let report = report_of(|n| 3.0 * n * n);
+        let power_law = report.power_law().unwrap();
+        assert!((power_law.b - 2.0).abs() < 1e-9);
+        assert!((power_law.a - 3.0).abs() < 1e-6);
+        assert!((power_law.predict(100) - 30000.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn power_law_ignores_unmeasurable_samples() {
+        let report = report_of(|n| n / 1000.0);
+        let power_law = report.power_law().unwrap();
+        assert!((power_law.b - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn power_law_not_enough_samples() {
+        assert_eq!(ObservationReport::new(vec!()).power_law(), None);
+        assert_eq!(ObservationReport::new(vec!(Sample { size: 4, elapsed_ns: vec![3] })).power_law(), None);
+        assert_eq!(report_of(|_| 0.0).power_law(), None);
+        assert_eq!(report_of(|_| 0.0).order_of_growth(), None);
+    }
+
+    #[test]
+    fn noise() {
+        assert!(report_of(|_| 0.0).is_noisy());
+        assert!(!report_of(|n| n * n).is_noisy());
+        let (lo, hi) = report_of(|n| n * n).exponent_confidence_interval(0.95).unwrap();
+        assert!((lo - 2.0).abs() < 1e-9 && (hi - 2.0).abs() < 1e-9);
+
+        let noisy = ObservationReport::new(vec!(
+            Sample { size: 1, elapsed_ns: vec![100, 1, 1000, 50, 5] },
+            Sample { size: 2, elapsed_ns: vec![1, 400, 2, 3000, 40] },
+            Sample { size: 4, elapsed_ns: vec![2000, 10, 3, 1, 700] },
+        ));
+        assert!(noisy.is_noisy());
+    }
+
+    #[test]
+    fn order_of_growth() {
+        assert_eq!(report_of(|_| 50.0).order_of_growth(), Some(OrderOfGrowth::Constant));
+        assert_eq!(report_of(|n| 50.0 * n.log2() + 1.0).order_of_growth(), Some(OrderOfGrowth::Logarithmic));
+        assert_eq!(report_of(|n| 5.0 * n).order_of_growth(), Some(OrderOfGrowth::Linear));
+        assert_eq!(report_of(|n| n * n.log2()).order_of_growth(), Some(OrderOfGrowth::Linearithmic));
+        assert_eq!(report_of(|n| n * n / 1000.0).order_of_growth(), Some(OrderOfGrowth::Quadratic));
+        assert_eq!(report_of(|n| n * n * n / 1_000_000.0).order_of_growth(), Some(OrderOfGrowth::Cubic));
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let report = ObservationReport::new(vec!(
+            Sample { size: 1, elapsed_ns: vec![10, 12] },
+            Sample { size: 2, elapsed_ns: vec![21] },
+        ));
+
+        let mut csv = vec!();
+        report.write_csv(&mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv.clone()).unwrap(), "size,elapsed_ns\n1,10\n1,12\n2,21\n");
+
+        assert_eq!(ObservationReport::read_csv(&csv[..]).unwrap(), report);
+    }
+
+    #[test]
+    fn csv_errors() {
+        assert!(ObservationReport::read_csv("size,elapsed_ns\n1,10,3\n".as_bytes()).is_err());
+        assert!(ObservationReport::read_csv("size,elapsed_ns\n1,ten\n".as_bytes()).is_err());
+        assert_eq!(ObservationReport::read_csv("size,elapsed_ns\n".as_bytes()).unwrap(), ObservationReport::new(vec!()));
+    }
+
+    #[test]
+    fn json() {
+        let report = ObservationReport::new(vec!(
+            Sample { size: 1, elapsed_ns: vec![6, 10] },
+            Sample { size: 2, elapsed_ns: vec![16] },
+            Sample { size: 4, elapsed_ns: vec![32] },
+            Sample { size: 8, elapsed_ns: vec![64] },
+        ));
+
+        let mut json = vec!();
+        report.write_json(&mut json).unwrap();
+        assert_eq!(String::from_utf8(json).unwrap(), concat!(
+            "{\n",
+            "  \"samples\": [\n",
+            "    {\"size\": 1, \"elapsed_ns\": [6, 10], \"median_ns\": 8, \"mean_ns\": 8, \"std_dev_ns\": 2.8284271247461903},\n",
+            "    {\"size\": 2, \"elapsed_ns\": [16], \"median_ns\": 16, \"mean_ns\": 16, \"std_dev_ns\": 0},\n",
+            "    {\"size\": 4, \"elapsed_ns\": [32], \"median_ns\": 32, \"mean_ns\": 32, \"std_dev_ns\": 0},\n",
+            "    {\"size\": 8, \"elapsed_ns\": [64], \"median_ns\": 64, \"mean_ns\": 64, \"std_dev_ns\": 0}\n",
+            "  ],\n",
+            "  \"power_law\": {\"a\": 8, \"b\": 1},\n",
+            "  \"order_of_growth\": \"N\"\n",
+            "}\n"));
+    }
+
+    #[test]
+    fn regressions() {
+        let baseline = ObservationReport::new(vec!(
+            Sample { size: 1, elapsed_ns: vec![10, 10, 10] },
+            Sample { size: 2, elapsed_ns: vec![20, 20, 20] },
+            Sample { size: 4, elapsed_ns: vec![40, 40, 40] },
+        ));
+        let report = ObservationReport::new(vec!(
+            Sample { size: 1, elapsed_ns: vec![11, 11, 11] },
+            Sample { size: 2, elapsed_ns: vec![30, 30, 30] },
+            Sample { size: 4, elapsed_ns: vec![10, 60, 70] },
+            Sample { size: 8, elapsed_ns: vec![200, 200, 200] },
+        ));
+
+        assert_eq!(report.regressions(&baseline, 0.2), vec!(Regression { size: 2, baseline_median: 20.0, median: 30.0 }));
+        assert_eq!(report.regressions(&report, 0.2), vec!());
+    }
+
+    #[test]
+    fn track_memory() {
+        let (vec, usage) = track(|| {
+            let mut vec = vec![0u64; 100];
+            vec.push(1);
+            let _temporary = vec![0u8; 1000];
+            vec
+        });
+        assert_eq!(usage.allocations, 2);
+        assert_eq!(usage.reallocations, 1);
+        assert_eq!(usage.deallocations, 1);
+        assert_eq!(usage.live_bytes, 8 * vec.capacity() as isize);
+        assert_eq!(usage.peak_bytes, 8 * vec.capacity() as isize + 1000);
+
+        let bytes = 8 * vec.capacity() as isize;
+        let ((), usage) = track(|| drop(vec));
+        assert_eq!(usage, MemoryUsage { allocations: 0, reallocations: 0, deallocations: 1, live_bytes: -bytes, peak_bytes: 0 });
+    }
+
+    #[test]
+    fn nested_track() {
+        let ((inner, _vec), outer) = track(|| {
+            let _vec = vec![0u8; 10];
+            let (_, inner) = track(|| vec![0u8; 20]);
+            (inner, _vec)
+        });
+        assert_eq!(inner.live_bytes, 20);
+        assert_eq!(inner.peak_bytes, 20);
+        assert_eq!(outer.allocations, 2);
+        assert_eq!(outer.deallocations, 1);
+        assert_eq!(outer.live_bytes, 10);
+        assert_eq!(outer.peak_bytes, 30);
+    }
+
+    #[test]
+    fn memory_report() {
+        let report = observe_memory::<Vec<i32>>(64);
+        let sizes: Vec<usize> = report.samples().iter().map(|sample| sample.size).collect();
+        assert_eq!(sizes, vec![1, 2, 4, 8, 16, 32]);
+        for sample in report.samples() {
+            assert_eq!(sample.prepare.live_bytes, 4 * sample.size as isize);
+            assert_eq!(sample.run.live_bytes, 0);
+        }
+        let power_law = report.power_law().unwrap();
+        assert!((power_law.a - 4.0).abs() < 1e-9);
+        assert!((power_law.b - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn relative_speed_for_the_largest_common_size() {
+        let slow = report_of(|x| 3.0 * x);
+        // from size 4 until size 512
+        let fast = ObservationReport::new(slow.samples()[2..10].iter()
+            .map(|sample| Sample { size: sample.size, elapsed_ns: vec![sample.elapsed_ns[0] / 3] })
+            .collect());
+        assert_eq!(relative_speed(&slow, &fast), Some((512, 3.0)));
+        assert_eq!(relative_speed(&fast, &slow), Some((512, 1.0 / 3.0)));
+
+        let other_sizes = ObservationReport::new(vec![Sample { size: 3, elapsed_ns: vec![9] }]);
+        assert_eq!(relative_speed(&slow, &other_sizes), None);
+        assert_eq!(relative_speed(&ObservationReport::new(vec![]), &slow), None);
+    }
+}
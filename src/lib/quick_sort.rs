@@ -1,60 +1,424 @@
 extern crate rand;
 
 use self::rand::ThreadRng;
+use std::cmp::Ordering;
+use std::ops::{Index, IndexMut};
+use ::index_ops::{Length, Swap};
 use knuth_shuffle::knuth_shuffle;
+use ::insertion_sort::insertion_sort_by_less;
+use ::max_pq::heap_sort_by_less;
+use ::sub_range::SubRange;
 
-/// sorts a vector with the quick_sort algorithm
-pub fn quick_sort<Item>(vec: &mut Vec<Item>, rng: &mut ThreadRng)
-    where Item: PartialOrd {
+/// sorts a collection with the quick_sort algorithm
+///
+/// Quick sort is not stable: partitioning exchanges elements over long distances, past elements
+/// with equal keys.
+pub fn quick_sort<Coll, Item>(vec: &mut Coll, rng: &mut ThreadRng)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// sorts like [`quick_sort`], in the order defined by the compare function
+///
+/// [`quick_sort`]: fn.quick_sort.html
+pub fn quick_sort_by<Coll, Item, F>(vec: &mut Coll, rng: &mut ThreadRng, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// sorts like [`quick_sort`], in the order of the keys that the key function extracts
+///
+/// [`quick_sort`]: fn.quick_sort.html
+pub fn quick_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, rng: &mut ThreadRng, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn quick_sort_by_less<Coll, Item, F>(vec: &mut Coll, rng: &mut ThreadRng, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     knuth_shuffle(vec, rng);
 
-    let size = vec.len();
-    sort(vec, 0, size);
+    let size = vec.length();
+    sort(vec, 0, size, less);
 }
 
-fn sort<Item>(vec: &mut Vec<Item>, lo: usize, hi: usize)
-    where Item: PartialOrd {
+fn sort<Coll, Item, F>(vec: &mut Coll, lo: usize, hi: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     if hi <= lo + 1 {
         return;
     } else {
-        let k = partition(vec, lo, hi);
-        sort(vec, lo, k);
-        sort(vec, k + 1, hi);
+        let k = partition_by_less(vec, lo, hi, less);
+        sort(vec, lo, k, less);
+        sort(vec, k + 1, hi, less);
     }
 }
 
-/// sorts a vector with the three_way_quick_sort algorithm
-pub fn three_way_quick_sort<Item>(vec: &mut Vec<Item>, rng: &mut ThreadRng)
-    where Item: PartialOrd {
+/// sorts a collection with the three_way_quick_sort algorithm
+///
+/// Like [`quick_sort`], it is not stable.
+///
+/// [`quick_sort`]: fn.quick_sort.html
+pub fn three_way_quick_sort<Coll, Item>(vec: &mut Coll, rng: &mut ThreadRng)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    three_way_quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// sorts like [`three_way_quick_sort`], in the order defined by the compare function
+///
+/// [`three_way_quick_sort`]: fn.three_way_quick_sort.html
+pub fn three_way_quick_sort_by<Coll, Item, F>(vec: &mut Coll, rng: &mut ThreadRng, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    three_way_quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// sorts like [`three_way_quick_sort`], in the order of the keys that the key function extracts
+///
+/// [`three_way_quick_sort`]: fn.three_way_quick_sort.html
+pub fn three_way_quick_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, rng: &mut ThreadRng, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    three_way_quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn three_way_quick_sort_by_less<Coll, Item, F>(vec: &mut Coll, rng: &mut ThreadRng, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     knuth_shuffle(vec, rng);
 
-    let size = vec.len();
-    three_way_sort(vec, 0, size);
+    let size = vec.length();
+    three_way_sort(vec, 0, size, less);
 }
 
-fn three_way_sort<Item>(vec: &mut Vec<Item>, lo: usize, hi: usize)
-    where Item: PartialOrd {
+fn three_way_sort<Coll, Item, F>(vec: &mut Coll, lo: usize, hi: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     if hi <= lo + 1 {
         return;
     } else {
-        let (lt, gt) = three_way_partition(vec, lo, hi);
-        three_way_sort(vec, lo, lt);
-        three_way_sort(vec, gt, hi);
+        let (lt, gt) = three_way_partition_by_less(vec, lo, hi, less);
+        three_way_sort(vec, lo, lt, less);
+        three_way_sort(vec, gt, hi, less);
+    }
+}
+
+/// Sorts a collection with Yaroslavskiy's dual-pivot quick sort: it partitions by two pivots into
+/// three parts, the elements less than the smaller pivot, those between the pivots, and those
+/// greater than the larger pivot.
+///
+/// Like [`quick_sort`], it is not stable.
+///
+/// [`quick_sort`]: fn.quick_sort.html
+pub fn dual_pivot_quick_sort<Coll, Item>(vec: &mut Coll, rng: &mut ThreadRng)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    dual_pivot_quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// sorts like [`dual_pivot_quick_sort`], in the order defined by the compare function
+///
+/// [`dual_pivot_quick_sort`]: fn.dual_pivot_quick_sort.html
+pub fn dual_pivot_quick_sort_by<Coll, Item, F>(vec: &mut Coll, rng: &mut ThreadRng, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    dual_pivot_quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// sorts like [`dual_pivot_quick_sort`], in the order of the keys that the key function extracts
+///
+/// [`dual_pivot_quick_sort`]: fn.dual_pivot_quick_sort.html
+pub fn dual_pivot_quick_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, rng: &mut ThreadRng, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    dual_pivot_quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn dual_pivot_quick_sort_by_less<Coll, Item, F>(vec: &mut Coll, rng: &mut ThreadRng, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    knuth_shuffle(vec, rng);
+
+    let size = vec.length();
+    dual_pivot_sort(vec, 0, size, less);
+}
+
+fn dual_pivot_sort<Coll, Item, F>(vec: &mut Coll, lo: usize, hi: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    if hi > lo + 1 {
+        let (lt, gt) = dual_pivot_partition_by_less(vec, lo, hi, less);
+        dual_pivot_sort(vec, lo, lt, less);
+        // with equal pivots, the elements between them are all equal
+        if less(&vec[lt], &vec[gt]) {
+            dual_pivot_sort(vec, lt + 1, gt, less);
+        }
+        dual_pivot_sort(vec, gt + 1, hi, less);
+    }
+}
+
+/// Sorts a collection with quick sort, partitioning with the fast three way partitioning by
+/// Bentley and McIlroy, see [`fast_three_way_partition`].
+///
+/// Like [`quick_sort`], it is not stable.
+///
+/// [`fast_three_way_partition`]: fn.fast_three_way_partition.html
+/// [`quick_sort`]: fn.quick_sort.html
+pub fn fast_three_way_quick_sort<Coll, Item>(vec: &mut Coll, rng: &mut ThreadRng)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    fast_three_way_quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// sorts like [`fast_three_way_quick_sort`], in the order defined by the compare function
+///
+/// [`fast_three_way_quick_sort`]: fn.fast_three_way_quick_sort.html
+pub fn fast_three_way_quick_sort_by<Coll, Item, F>(vec: &mut Coll, rng: &mut ThreadRng, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    fast_three_way_quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// sorts like [`fast_three_way_quick_sort`], in the order of the keys that the key function extracts
+///
+/// [`fast_three_way_quick_sort`]: fn.fast_three_way_quick_sort.html
+pub fn fast_three_way_quick_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, rng: &mut ThreadRng, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    fast_three_way_quick_sort_by_less(vec, rng, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn fast_three_way_quick_sort_by_less<Coll, Item, F>(vec: &mut Coll, rng: &mut ThreadRng, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    knuth_shuffle(vec, rng);
+
+    let size = vec.length();
+    fast_three_way_sort(vec, 0, size, less);
+}
+
+fn fast_three_way_sort<Coll, Item, F>(vec: &mut Coll, lo: usize, hi: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    if hi > lo + 1 {
+        let (lt, gt) = fast_three_way_partition_by_less(vec, lo, hi, less);
+        fast_three_way_sort(vec, lo, lt, less);
+        fast_three_way_sort(vec, gt, hi, less);
+    }
+}
+
+/// partitions of at most this size are sorted with insertion sort by [`intro_sort`]
+///
+/// [`intro_sort`]: fn.intro_sort.html
+const INSERTION_CUTOFF: usize = 10;
+
+/// partitions larger than this take Tukey's ninther as pivot, smaller ones the median of 3
+const NINTHER_CUTOFF: usize = 40;
+
+/// Sorts a collection with quick sort, improved like the introsort algorithm, without shuffling
+/// it first: the pivot is the median of 3 elements, or Tukey's ninther for large partitions, small
+/// partitions are sorted with insertion sort, and only the smaller partition is sorted recursively.
+///
+/// When the recursion gets deeper than 2 lg N, the partition is sorted with heap sort instead, so
+/// that the sort takes time proportional to N lg N also in the worst case.
+///
+/// Like [`quick_sort`], it is not stable.
+///
+/// [`quick_sort`]: fn.quick_sort.html
+pub fn intro_sort<Coll, Item>(vec: &mut Coll)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    intro_sort_by_less(vec, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// sorts like [`intro_sort`], in the order defined by the compare function
+///
+/// [`intro_sort`]: fn.intro_sort.html
+pub fn intro_sort_by<Coll, Item, F>(vec: &mut Coll, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    intro_sort_by_less(vec, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// sorts like [`intro_sort`], in the order of the keys that the key function extracts
+///
+/// [`intro_sort`]: fn.intro_sort.html
+pub fn intro_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    intro_sort_by_less(vec, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn intro_sort_by_less<Coll, Item, F>(vec: &mut Coll, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
+    intro_sort_range(vec, 0, size, 2 * lg(size), less);
+}
+
+/// the binary logarithm of size, rounded down, or 0 for the size 0
+pub(crate) fn lg(size: usize) -> usize {
+    let mut lg = 0;
+    while size >> (lg + 1) > 0 {
+        lg += 1;
+    }
+    lg
+}
+
+/// sorts the range from lo to (excluding) hi, with heap sort, once depth reaches 0
+pub(crate) fn intro_sort_range<Coll, Item, F>(vec: &mut Coll, mut lo: usize, mut hi: usize, mut depth: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    loop {
+        if hi - lo <= INSERTION_CUTOFF {
+            insertion_sort_by_less(&mut SubRange::new(vec, lo, hi), less);
+            return;
+        }
+        if depth == 0 {
+            heap_sort_by_less(&mut SubRange::new(vec, lo, hi), less);
+            return;
+        }
+        depth -= 1;
+
+        let pivot = choose_pivot(vec, lo, hi, less);
+        vec.swap(lo, pivot);
+        let k = partition_at_equal_keys(vec, lo, hi, less);
+
+        // recurse into the smaller partition, and continue with the larger one, so that the
+        // recursion is at most lg N deep
+        if k - lo < hi - k {
+            intro_sort_range(vec, lo, k, depth, less);
+            lo = k + 1;
+        } else {
+            intro_sort_range(vec, k + 1, hi, depth, less);
+            hi = k;
+        }
+    }
+}
+
+/// the index of the median of 3 elements, or of Tukey's ninther for large ranges
+pub(crate) fn choose_pivot<Coll, Item, F>(vec: &Coll, lo: usize, hi: usize, less: &mut F) -> usize
+    where Coll: ?Sized + Index<usize, Output=Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let mid = lo + (hi - lo) / 2;
+    if hi - lo > NINTHER_CUTOFF {
+        let eps = (hi - lo) / 8;
+        let m1 = median_of_3(vec, lo, lo + eps, lo + 2 * eps, less);
+        let m2 = median_of_3(vec, mid - eps, mid, mid + eps, less);
+        let m3 = median_of_3(vec, hi - 1 - 2 * eps, hi - 1 - eps, hi - 1, less);
+        median_of_3(vec, m1, m2, m3, less)
+    } else {
+        median_of_3(vec, lo, mid, hi - 1, less)
     }
 }
 
-/// selects the kth largest element in a vector
-pub fn quick_select<Item>(vec: &mut Vec<Item>, k: usize, rng: &mut ThreadRng) -> Item
-    where Item: PartialOrd + Copy {
+/// the index of the median of the elements at the indices a, b and c
+fn median_of_3<Coll, Item, F>(vec: &Coll, a: usize, b: usize, c: usize, less: &mut F) -> usize
+    where Coll: ?Sized + Index<usize, Output=Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    if less(&vec[a], &vec[b]) {
+        if less(&vec[b], &vec[c]) { b } else if less(&vec[a], &vec[c]) { c } else { a }
+    } else if less(&vec[a], &vec[c]) {
+        a
+    } else if less(&vec[b], &vec[c]) {
+        c
+    } else {
+        b
+    }
+}
+
+/// Partitions the range from lo to (excluding) hi by its first element, like [`partition`], but
+/// stops scanning at elements that are equal to it. That way, many equal keys are divided evenly
+/// between both partitions.
+///
+/// [`partition`]: fn.partition.html
+pub(crate) fn partition_at_equal_keys<Coll, Item, F>(vec: &mut Coll, lo: usize, hi: usize, less: &mut F) -> usize
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let mut i = lo;
+    let mut j = hi;
+    loop {
+        i += 1;
+        while i < hi - 1 && less(&vec[i], &vec[lo]) {
+            i += 1;
+        }
+        j -= 1;
+        // stops at lo at the latest, as the pivot is not less than itself
+        while less(&vec[lo], &vec[j]) {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        vec.swap(i, j);
+    }
+    vec.swap(lo, j);
+    j
+}
+
+/// Selects the kth smallest element in a collection, counting from 0, with quick select: it
+/// shuffles the collection and partitions it, until the partitioning element is at index k. The
+/// collection is left partitioned: the elements before index k are not greater than the selected
+/// one, and those after it are not less.
+///
+/// It takes linear time on average.
+///
+/// # Panics
+/// Panics if k is not less than the length of the collection.
+pub fn quick_select<'a, Coll, Item>(vec: &'a mut Coll, k: usize, rng: &mut ThreadRng) -> &'a Item
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    select_by_less(vec, k, rng, &mut |a: &Item, b: &Item| a < b);
+    &vec[k]
+}
+
+/// selects like [`quick_select`], in the order defined by the compare function
+///
+/// [`quick_select`]: fn.quick_select.html
+pub fn quick_select_by<'a, Coll, Item, F>(vec: &'a mut Coll, k: usize, rng: &mut ThreadRng, mut compare: F) -> &'a Item
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    select_by_less(vec, k, rng, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+    &vec[k]
+}
+
+/// selects like [`quick_select`], in the order of the keys that the key function extracts
+///
+/// [`quick_select`]: fn.quick_select.html
+pub fn quick_select_by_key<'a, Coll, Item, K, F>(vec: &'a mut Coll, k: usize, rng: &mut ThreadRng, mut key: F) -> &'a Item
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    select_by_less(vec, k, rng, &mut |a: &Item, b: &Item| key(a) < key(b));
+    &vec[k]
+}
+
+/// shuffles the collection and partitions it, until the kth smallest element is at index k
+pub(crate) fn select_by_less<Coll, Item, F>(vec: &mut Coll, k: usize, rng: &mut ThreadRng, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
+    assert!(k < size, "index {} is out of range for a collection of length {}", k, size);
+
     knuth_shuffle(vec, rng);
 
     let mut lo = 0usize;
-    let mut hi = vec.len();
+    let mut hi = size;
 
     loop {
-        let l = partition(vec, lo, hi);
+        let l = partition_by_less(vec, lo, hi, less);
         if l == k {
-            return vec[k];
+            return;
         }
         if l > k {
             hi = l;
@@ -64,10 +428,17 @@ pub fn quick_select<Item>(vec: &mut Vec<Item>, k: usize, rng: &mut ThreadRng) ->
     }
 }
 
-/// partitions a vector by its first element.
+/// partitions a collection by its first element.
 /// returns the index of that element after partitioning.
-pub fn partition<Item>(vec: &mut Vec<Item>, lo: usize, hi: usize) -> usize
-    where Item: PartialOrd {
+pub fn partition<Coll, Item>(vec: &mut Coll, lo: usize, hi: usize) -> usize
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    partition_by_less(vec, lo, hi, &mut |a: &Item, b: &Item| a < b)
+}
+
+fn partition_by_less<Coll, Item, F>(vec: &mut Coll, lo: usize, hi: usize, less: &mut F) -> usize
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     if hi <= lo + 1 {
         lo
     } else {
@@ -75,11 +446,11 @@ pub fn partition<Item>(vec: &mut Vec<Item>, lo: usize, hi: usize) -> usize
         let mut j = hi - 1;
 
         while i <= j {
-            while i < hi && vec[i] <= vec[lo] {
+            while i < hi && !less(&vec[lo], &vec[i]) {
                 i = i + 1;
             }
 
-            while j > lo && vec[j] >= vec[lo] {
+            while j > lo && !less(&vec[j], &vec[lo]) {
                 j = j - 1;
             }
 
@@ -93,11 +464,18 @@ pub fn partition<Item>(vec: &mut Vec<Item>, lo: usize, hi: usize) -> usize
     }
 }
 
-/// partitions a vector by its first element
+/// partitions a collection by its first element
 /// returns two indices, indicating the range of elements
 /// that are equal to that element, after partitioning
-pub fn three_way_partition<Item>(vec: &mut Vec<Item>, lo: usize, hi: usize) -> (usize, usize)
-    where Item: PartialOrd {
+pub fn three_way_partition<Coll, Item>(vec: &mut Coll, lo: usize, hi: usize) -> (usize, usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    three_way_partition_by_less(vec, lo, hi, &mut |a: &Item, b: &Item| a < b)
+}
+
+pub(crate) fn three_way_partition_by_less<Coll, Item, F>(vec: &mut Coll, lo: usize, hi: usize, less: &mut F) -> (usize, usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     if hi <= lo + 1 {
         (lo, hi)
     } else {
@@ -106,12 +484,12 @@ pub fn three_way_partition<Item>(vec: &mut Vec<Item>, lo: usize, hi: usize) -> (
         let mut gt = hi;
 
         while gt > i {
-            if vec[i] < vec[lt] {
+            if less(&vec[i], &vec[lt]) {
                 vec.swap(lt, i);
                 lt = lt + 1;
                 i = i + 1;
             }
-            else if vec[i] > vec[lt] {
+            else if less(&vec[lt], &vec[i]) {
                 vec.swap(i, gt - 1);
                 gt = gt - 1;
             }
@@ -123,11 +501,135 @@ pub fn three_way_partition<Item>(vec: &mut Vec<Item>, lo: usize, hi: usize) -> (
     }
 }
 
+/// partitions a collection by two pivots, the smaller one of its first and its last element,
+/// and the larger one.
+/// returns the indices of the two pivots after partitioning: the elements before the first one
+/// are less than it, the elements after the second one are greater than it, and the elements
+/// between them are neither less than the first nor greater than the second pivot.
+pub fn dual_pivot_partition<Coll, Item>(vec: &mut Coll, lo: usize, hi: usize) -> (usize, usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    dual_pivot_partition_by_less(vec, lo, hi, &mut |a: &Item, b: &Item| a < b)
+}
+
+fn dual_pivot_partition_by_less<Coll, Item, F>(vec: &mut Coll, lo: usize, hi: usize, less: &mut F) -> (usize, usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    if hi <= lo + 1 {
+        (lo, lo)
+    } else {
+        let last = hi - 1;
+        if less(&vec[last], &vec[lo]) {
+            vec.swap(lo, last);
+        }
+
+        let mut lt = lo + 1;
+        let mut i = lo + 1;
+        let mut gt = last;
+
+        while gt > i {
+            if less(&vec[i], &vec[lo]) {
+                vec.swap(lt, i);
+                lt += 1;
+                i += 1;
+            }
+            else if less(&vec[last], &vec[i]) {
+                vec.swap(i, gt - 1);
+                gt -= 1;
+            }
+            else {
+                i += 1;
+            }
+        }
+
+        vec.swap(lo, lt - 1);
+        vec.swap(last, gt);
+        (lt - 1, gt)
+    }
+}
+
+/// partitions a collection by its first element, like [`three_way_partition`], with the fast
+/// three way partitioning by Bentley and McIlroy: while scanning from both ends, like
+/// [`partition`], it swaps the elements equal to the partitioning element to the ends, and
+/// finally swaps them into the middle. That way, it takes fewer exchanges when there are few
+/// equal keys.
+/// returns two indices, indicating the range of elements
+/// that are equal to that element, after partitioning
+///
+/// [`three_way_partition`]: fn.three_way_partition.html
+/// [`partition`]: fn.partition.html
+pub fn fast_three_way_partition<Coll, Item>(vec: &mut Coll, lo: usize, hi: usize) -> (usize, usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    fast_three_way_partition_by_less(vec, lo, hi, &mut |a: &Item, b: &Item| a < b)
+}
+
+fn fast_three_way_partition_by_less<Coll, Item, F>(vec: &mut Coll, lo: usize, hi: usize, less: &mut F) -> (usize, usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    if hi <= lo + 1 {
+        (lo, hi)
+    } else {
+        let last = hi - 1;
+        // lo..=p and q..hi hold the elements equal to the partitioning element at lo
+        let mut p = lo;
+        let mut q = hi;
+        let mut i = lo;
+        let mut j = hi;
+
+        loop {
+            i += 1;
+            while i < last && less(&vec[i], &vec[lo]) {
+                i += 1;
+            }
+            // stops at lo at the latest, as the partitioning element is not less than itself
+            j -= 1;
+            while less(&vec[lo], &vec[j]) {
+                j -= 1;
+            }
+
+            if i == j && !less(&vec[i], &vec[lo]) && !less(&vec[lo], &vec[i]) {
+                p += 1;
+                vec.swap(p, i);
+            }
+            if i >= j {
+                break;
+            }
+
+            vec.swap(i, j);
+            if !less(&vec[i], &vec[lo]) {
+                p += 1;
+                vec.swap(p, i);
+            }
+            if !less(&vec[lo], &vec[j]) {
+                q -= 1;
+                vec.swap(q, j);
+            }
+        }
+
+        // swap the equal elements from the ends into the middle
+        let lt = j - (p - lo);
+        for k in lo..p + 1 {
+            vec.swap(k, j - (k - lo));
+        }
+        let gt = j + 1 + (hi - q);
+        for k in q..hi {
+            vec.swap(k, j + 1 + (last - k));
+        }
+        (lt, gt)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{partition, three_way_partition, quick_sort, three_way_quick_sort, quick_select};
-    use super::rand::thread_rng;
-    use ::is_sorted::sort_some;
+    use super::{quick_sort_by, quick_sort_by_key, three_way_quick_sort_by, three_way_quick_sort_by_key, quick_select_by, quick_select_by_key};
+    use super::{dual_pivot_partition, dual_pivot_quick_sort, dual_pivot_quick_sort_by, dual_pivot_quick_sort_by_key};
+    use super::{fast_three_way_partition, fast_three_way_quick_sort, fast_three_way_quick_sort_by, fast_three_way_quick_sort_by_key};
+    use super::{intro_sort, intro_sort_by, intro_sort_by_key, intro_sort_range, lg, median_of_3, partition_at_equal_keys};
+    use super::rand::{thread_rng, Rng};
+    use ::is_sorted::{is_sorted, sort_some, sort_some_by, sort_some_slices};
+    use ::stability::sorts_stably;
 
     fn is_partitioned<Item>(vec: &Vec<Item>, k: usize) -> bool
         where Item: PartialOrd {
@@ -157,7 +659,7 @@ mod tests {
         }
     }
 
-    fn is_three_way_partitioned<Item>(vec: &Vec<Item>, lt: usize, gt: usize) -> bool
+    fn is_three_way_partitioned<Item>(vec: &[Item], lt: usize, gt: usize) -> bool
         where Item: PartialOrd {
         let size = vec.len();
         if size == 0 {
@@ -189,6 +691,107 @@ mod tests {
         }
     }
 
+    fn is_dual_pivot_partitioned<Item>(vec: &[Item], lt: usize, gt: usize) -> bool
+        where Item: PartialOrd {
+        let size = vec.len();
+        if size == 0 {
+            true
+        } else {
+            assert!(lt <= gt);
+            assert!(gt < size);
+            let p = &vec[lt];
+            let q = &vec[gt];
+
+            p <= q &&
+                vec[..lt].iter().all(|x| x < p) &&
+                vec[lt..gt].iter().all(|x| x >= p && x <= q) &&
+                vec[gt + 1..].iter().all(|x| x > q)
+        }
+    }
+
+    // partitions random vectors with the partition function, and checks the partitioning with
+    // the check, and that no element was lost
+    fn partition_random<P, C>(mut partition: P, is_partitioned: C)
+        where P: FnMut(&mut Vec<u32>, usize, usize) -> (usize, usize),
+              C: Fn(&[u32], usize, usize) -> bool {
+        let mut rng = thread_rng();
+        for size in 1..40 {
+            for max in &[2, 5, 1000] {
+                let mut vec: Vec<u32> = (0..size).map(|_| rng.gen_range(0, *max)).collect();
+                let mut elements = vec.clone();
+                let (lt, gt) = partition(&mut vec, 0, size);
+                assert!(is_partitioned(&vec, lt, gt), "not partitioned: {:?}, {}, {}", vec, lt, gt);
+
+                let mut partitioned = vec.clone();
+                partitioned.sort();
+                elements.sort();
+                assert_eq!(partitioned, elements);
+            }
+        }
+    }
+
+    #[test]
+    fn partition_random_vectors() {
+        partition_random(three_way_partition, is_three_way_partitioned);
+        partition_random(fast_three_way_partition, is_three_way_partitioned);
+        partition_random(dual_pivot_partition, is_dual_pivot_partitioned);
+    }
+
+    #[test]
+    fn fast_three_way_partition_some() {
+        let mut vec = vec![4, 10, 2, 8, 4, 3, 6, 8, 2, 4, 3, 0, 3];
+        let (lt, gt) = fast_three_way_partition(&mut vec, 0, 13);
+        assert_eq!((lt, gt), (6, 9));
+        assert!(is_three_way_partitioned(&vec, lt, gt));
+
+        let mut vec = vec![5, 5, 5, 5];
+        assert_eq!(fast_three_way_partition(&mut vec, 0, 4), (0, 4));
+
+        let mut vec = vec![7, 1, 9, 3, 5];
+        let (lt, gt) = fast_three_way_partition(&mut vec, 1, 4);
+        assert_eq!((lt, gt), (1, 2));
+        assert_eq!(vec, vec![7, 1, 9, 3, 5]);
+    }
+
+    #[test]
+    fn dual_pivot_partition_some() {
+        let mut vec = vec![8, 10, 2, 8, 3, 6, 1, 2, 9, 0, 3];
+        let (lt, gt) = dual_pivot_partition(&mut vec, 0, 11);
+        assert_eq!(vec[lt], 3);
+        assert_eq!(vec[gt], 8);
+        assert!(is_dual_pivot_partitioned(&vec, lt, gt));
+
+        let mut vec = vec![4, 4, 4];
+        assert_eq!(dual_pivot_partition(&mut vec, 0, 3), (0, 2));
+        let mut vec = vec![4];
+        assert_eq!(dual_pivot_partition(&mut vec, 0, 1), (0, 0));
+    }
+
+    #[test]
+    fn dual_pivot_and_fast_three_way_quick_sort_some() {
+        let mut rng = thread_rng();
+        sort_some::<u32>(&mut rng, |vec| dual_pivot_quick_sort(vec, &mut thread_rng()));
+        sort_some::<u32>(&mut rng, |vec| fast_three_way_quick_sort(vec, &mut thread_rng()));
+        sort_some_slices::<u32>(&mut rng, |slice| dual_pivot_quick_sort(slice, &mut thread_rng()));
+        sort_some_slices::<u32>(&mut rng, |slice| fast_three_way_quick_sort(slice, &mut thread_rng()));
+        sort_some_by(&mut rng,
+                     |slice| dual_pivot_quick_sort_by(slice, &mut thread_rng(), |a, b| b.cmp(a)),
+                     |slice| dual_pivot_quick_sort_by_key(slice, &mut thread_rng(), |pair| pair.1));
+        sort_some_by(&mut rng,
+                     |slice| fast_three_way_quick_sort_by(slice, &mut thread_rng(), |a, b| b.cmp(a)),
+                     |slice| fast_three_way_quick_sort_by_key(slice, &mut thread_rng(), |pair| pair.1));
+
+        // many duplicate keys
+        for _ in 0..10 {
+            let mut vec: Vec<u32> = (0..1000).map(|_| rng.gen_range(0, 3)).collect();
+            dual_pivot_quick_sort(&mut vec, &mut rng);
+            assert!(is_sorted(&vec));
+            let mut vec: Vec<u32> = (0..1000).map(|_| rng.gen_range(0, 3)).collect();
+            fast_three_way_quick_sort(&mut vec, &mut rng);
+            assert!(is_sorted(&vec));
+        }
+    }
+
     #[test]
     fn partition_some() {
         {
@@ -347,18 +950,146 @@ mod tests {
 
         let mut vec = vec![1];
         let result = quick_select(&mut vec, 0, &mut rng);
-        assert_eq!(result, 1);
+        assert_eq!(*result, 1);
 
         let mut vec = vec![3, 2, 6, 1];
         let result = quick_select(&mut vec, 2, &mut rng);
-        assert_eq!(result, 3);
+        assert_eq!(*result, 3);
 
         let mut vec = vec![3, 2, 3, 6, 3, 7, 3];
         let result = quick_select(&mut vec, 3, &mut rng);
-        assert_eq!(result, 3);
+        assert_eq!(*result, 3);
 
         let mut vec = vec![3, 2, 3, 6, 3, 7, 3];
         let result = quick_select(&mut vec, 5, &mut rng);
-        assert_eq!(result, 6);
+        assert_eq!(*result, 6);
+    }
+
+    #[test]
+    fn quick_select_partitions_non_copy_elements() {
+        let mut rng = thread_rng();
+        for size in 1..50 {
+            let mut vec: Vec<String> = (0..size).map(|_| rng.gen_range(0, 20).to_string()).collect();
+            let k = rng.gen_range(0, size);
+            let kth = quick_select(&mut vec, k, &mut rng).clone();
+
+            let mut sorted = vec.clone();
+            sorted.sort();
+            assert_eq!(kth, sorted[k]);
+            assert!(vec[..k].iter().all(|s| *s <= kth));
+            assert!(vec[k + 1..].iter().all(|s| *s >= kth));
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of range for a collection of length 3")]
+    fn quick_select_out_of_range() {
+        quick_select(&mut vec![1, 2, 3], 3, &mut thread_rng());
+    }
+
+    #[test]
+    fn quick_sort_slices() {
+        let mut rng = thread_rng();
+        sort_some_slices::<u32>(&mut rng, |slice| quick_sort(slice, &mut thread_rng()));
+        sort_some_slices::<u32>(&mut rng, |slice| three_way_quick_sort(slice, &mut thread_rng()));
+
+        let mut array = [7, 3, 5, 6, 2, 5, 1, 9];
+        quick_sort(&mut array, &mut rng);
+        assert_eq!(array, [1, 2, 3, 5, 5, 6, 7, 9]);
+        assert_eq!(*quick_select(&mut array[2..], 0, &mut rng), 3);
+    }
+
+    #[test]
+    fn quick_sort_by_some() {
+        let mut rng = thread_rng();
+        sort_some_by(&mut rng,
+                     |slice| quick_sort_by(slice, &mut thread_rng(), |a, b| b.cmp(a)),
+                     |slice| quick_sort_by_key(slice, &mut thread_rng(), |pair| pair.1));
+        sort_some_by(&mut rng,
+                     |slice| three_way_quick_sort_by(slice, &mut thread_rng(), |a, b| b.cmp(a)),
+                     |slice| three_way_quick_sort_by_key(slice, &mut thread_rng(), |pair| pair.1));
+    }
+
+    #[test]
+    fn quick_sort_is_not_stable() {
+        let mut rng = thread_rng();
+        assert!(!sorts_stably(&mut rng, |vec| quick_sort(vec, &mut thread_rng())));
+        assert!(!sorts_stably(&mut rng, |vec| three_way_quick_sort(vec, &mut thread_rng())));
+        assert!(!sorts_stably(&mut rng, intro_sort));
+        assert!(!sorts_stably(&mut rng, |vec| dual_pivot_quick_sort(vec, &mut thread_rng())));
+        assert!(!sorts_stably(&mut rng, |vec| fast_three_way_quick_sort(vec, &mut thread_rng())));
+    }
+
+    #[test]
+    fn intro_sort_some() {
+        let mut rng = thread_rng();
+        sort_some::<u32>(&mut rng, intro_sort);
+        sort_some_slices::<u32>(&mut rng, intro_sort);
+        sort_some_by(&mut rng,
+                     |slice| intro_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| intro_sort_by_key(slice, |pair| pair.1));
+    }
+
+    #[test]
+    fn intro_sort_patterns() {
+        let size = 1000;
+        let patterns: Vec<Vec<usize>> = vec![
+            (0..size).collect(),
+            (0..size).rev().collect(),
+            vec![7; size],
+            (0..size).map(|i| i.min(size - i)).collect(),
+            (0..size).map(|i| i % 17).collect(),
+        ];
+        for mut vec in patterns {
+            intro_sort(&mut vec);
+            assert!(is_sorted(&vec));
+        }
+    }
+
+    #[test]
+    fn intro_sort_falls_back_to_heap_sort() {
+        let mut vec = vec![5, 3, 9, 1, 8, 2, 7, 3, 6, 0, 4, 5, 11, 10];
+        intro_sort_range(&mut vec, 2, 14, 0, &mut |a: &i32, b: &i32| a < b);
+        assert_eq!(vec, vec![5, 3, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+    }
+
+    #[test]
+    fn test_median_of_3() {
+        let less = &mut |a: &i32, b: &i32| a < b;
+        for vec in &[[1, 2, 3], [1, 3, 2], [2, 1, 3], [2, 3, 1], [3, 1, 2], [3, 2, 1], [2, 2, 1], [2, 2, 2]] {
+            let median = median_of_3(vec, 0, 1, 2, less);
+            assert_eq!(vec[median], 2);
+        }
+    }
+
+    #[test]
+    fn test_lg() {
+        assert_eq!(lg(0), 0);
+        assert_eq!(lg(1), 0);
+        assert_eq!(lg(2), 1);
+        assert_eq!(lg(1023), 9);
+        assert_eq!(lg(1024), 10);
+    }
+
+    #[test]
+    fn partition_at_equal_keys_splits_in_the_middle() {
+        let mut vec = vec![3; 21];
+        assert_eq!(partition_at_equal_keys(&mut vec, 0, 21, &mut |a: &i32, b: &i32| a < b), 10);
+
+        let mut vec = vec![5, 9, 1, 5, 7, 3, 5, 8, 2, 6, 4];
+        let k = partition_at_equal_keys(&mut vec, 0, 11, &mut |a: &i32, b: &i32| a < b);
+        assert_eq!(vec[k], 5);
+        assert!(vec[..k].iter().all(|x| *x <= 5));
+        assert!(vec[k + 1..].iter().all(|x| *x >= 5));
+    }
+
+    #[test]
+    fn test_quick_select_by() {
+        let mut rng = thread_rng();
+
+        let mut vec = vec![(3, 'c'), (1, 'a'), (4, 'd'), (2, 'b'), (5, 'e')];
+        assert_eq!(quick_select_by(&mut vec, 0, &mut rng, |a, b| b.0.cmp(&a.0)), &(5, 'e'));
+        assert_eq!(quick_select_by(&mut vec, 3, &mut rng, |a, b| a.1.cmp(&b.1)), &(4, 'd'));
+        assert_eq!(quick_select_by_key(&mut vec, 1, &mut rng, |pair| pair.0), &(2, 'b'));
+    }
+}
@@ -0,0 +1,413 @@
+//! Sorts that do not compare keys, but use them as indices, as presented in the course:
+//!
+//! * [`key_indexed_counting_sort`], for small integer keys
+//! * [`lsd_sort`], the least-significant-digit-first radix sort for fixed-width keys, with
+//!   [`radix_sort`] for integers and [`lsd_string_sort`] for fixed-length strings
+//! * [`msd_sort`], the most-significant-digit-first radix sort for variable-length strings
+//! * and [`three_way_string_quick_sort`], which partitions strings by one character at a time.
+//!
+//! Except for the `Copy` keys of [`radix_sort`], the elements are moved with [`Swap`] only, so they
+//! need neither be `Copy` nor `Clone`.
+//!
+//! [`key_indexed_counting_sort`]: fn.key_indexed_counting_sort.html
+//! [`lsd_sort`]: fn.lsd_sort.html
+//! [`radix_sort`]: fn.radix_sort.html
+//! [`lsd_string_sort`]: fn.lsd_string_sort.html
+//! [`msd_sort`]: fn.msd_sort.html
+//! [`three_way_string_quick_sort`]: fn.three_way_string_quick_sort.html
+//! [`Swap`]: ../index_ops/trait.Swap.html
+//!
+//! # Example
+//! ```
+//! use algorithms::radix_sort::{msd_sort, radix_sort};
+//!
+//! let mut ids: Vec<u32> = vec![170, 45, 75, 90, 802, 24, 2, 66];
+//! radix_sort(&mut ids);
+//! assert_eq!(ids, vec![2, 24, 45, 66, 75, 90, 170, 802]);
+//!
+//! let mut words = vec!["she", "sells", "seashells", "by", "the", "sea", "shore"];
+//! msd_sort(&mut words);
+//! assert_eq!(words, vec!["by", "sea", "seashells", "sells", "she", "shore", "the"]);
+//! ```
+
+extern crate rand;
+
+use self::rand::ThreadRng;
+use std::ops::{Index, IndexMut};
+use ::index_ops::{Length, Swap};
+use ::insertion_sort::insertion_sort_by_less;
+use ::knuth_shuffle::knuth_shuffle;
+use ::sub_range::SubRange;
+
+/// the number of distinct bytes
+const RADIX: usize = 256;
+
+/// subarrays of at most this size are sorted with insertion sort by the string sorts
+const CUTOFF: usize = 15;
+
+/// Keys of a fixed number of bytes, that [`radix_sort`] sorts by.
+///
+/// [`radix_sort`]: fn.radix_sort.html
+pub trait RadixKey: Copy {
+    /// the number of bytes of a key
+    const BYTES: usize;
+
+    /// the d-th byte of the key, counting from the most significant one. The bytes compare like
+    /// the keys: the order of two keys is the order of their first differing bytes.
+    fn byte(&self, d: usize) -> usize;
+}
+
+macro_rules! unsigned_radix_key {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = ::std::mem::size_of::<$t>();
+
+            fn byte(&self, d: usize) -> usize {
+                ((*self >> (8 * (Self::BYTES - 1 - d))) & 0xff) as usize
+            }
+        }
+    )*}
+}
+
+macro_rules! signed_radix_key {
+    ($($t:ty => $u:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = ::std::mem::size_of::<$t>();
+
+            fn byte(&self, d: usize) -> usize {
+                // flipping the sign bit orders the negative keys before the positive ones
+                (*self as $u ^ (1 << (8 * Self::BYTES - 1))).byte(d)
+            }
+        }
+    )*}
+}
+
+unsigned_radix_key!(u8, u16, u32, u64, usize);
+signed_radix_key!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+
+/// Sorts by small integer keys, that the key function extracts, with key-indexed counting. The
+/// keys must be less than radix. The sort is stable, and takes time proportional to N + radix.
+///
+/// # Panics
+/// Panics if a key is not less than radix.
+pub fn key_indexed_counting_sort<Coll, Item, F>(vec: &mut Coll, radix: usize, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item) -> usize {
+    let keys: Vec<usize> = (0..vec.length()).map(|i| key(&vec[i])).collect();
+    sort_by_keys(vec, radix, &keys);
+}
+
+/// Sorts keys of width digits with the least-significant-digit-first radix sort: a stable
+/// [`key_indexed_counting_sort`] by each digit, from the last one to the first one.
+/// `digit(item, d)` returns the d-th digit of the key of item, counting from the most significant
+/// one, which must be less than radix.
+///
+/// The sort is stable, and takes time proportional to width · (N + radix).
+///
+/// [`key_indexed_counting_sort`]: fn.key_indexed_counting_sort.html
+pub fn lsd_sort<Coll, Item, F>(vec: &mut Coll, width: usize, radix: usize, mut digit: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, usize) -> usize {
+    for d in (0..width).rev() {
+        key_indexed_counting_sort(vec, radix, |item| digit(item, d));
+    }
+}
+
+/// Sorts integers, or other [`RadixKey`]s, like [`lsd_sort`], one byte at a time. As the keys are
+/// `Copy`, it copies them between two auxilary arrays, instead of exchanging them, and it skips
+/// the bytes that all keys share.
+///
+/// [`RadixKey`]: trait.RadixKey.html
+/// [`lsd_sort`]: fn.lsd_sort.html
+pub fn radix_sort<Coll, Item>(vec: &mut Coll)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length,
+          Item: RadixKey {
+    let size = vec.length();
+    let mut from: Vec<Item> = (0..size).map(|i| vec[i]).collect();
+    let mut to = from.clone();
+
+    for d in (0..Item::BYTES).rev() {
+        let mut count = [0usize; RADIX + 1];
+        for key in &from {
+            count[key.byte(d) + 1] += 1;
+        }
+        // all keys share this byte
+        if count.contains(&size) {
+            continue;
+        }
+        for r in 0..RADIX {
+            count[r + 1] += count[r];
+        }
+        for key in &from {
+            let byte = key.byte(d);
+            to[count[byte]] = *key;
+            count[byte] += 1;
+        }
+        ::std::mem::swap(&mut from, &mut to);
+    }
+
+    for (i, key) in from.into_iter().enumerate() {
+        vec[i] = key;
+    }
+}
+
+/// Sorts strings by their first width bytes with [`lsd_sort`]. Strings that share those bytes
+/// keep their order.
+///
+/// # Panics
+/// Panics if a string is shorter than width.
+///
+/// [`lsd_sort`]: fn.lsd_sort.html
+pub fn lsd_string_sort<Coll, Item>(vec: &mut Coll, width: usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: AsRef<[u8]> {
+    lsd_sort(vec, width, RADIX, |item, d| item.as_ref()[d] as usize);
+}
+
+/// Sorts strings with the most-significant-digit-first radix sort: it sorts by the first byte with
+/// [`key_indexed_counting_sort`], and then sorts the strings of each first byte by the next byte,
+/// recursively. Small subarrays are sorted with insertion sort. Strings are ordered like their
+/// bytes, which is the order of the characters for UTF-8.
+///
+/// [`key_indexed_counting_sort`]: fn.key_indexed_counting_sort.html
+pub fn msd_sort<Coll, Item>(vec: &mut Coll)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: AsRef<[u8]> {
+    let size = vec.length();
+    msd_sort_range(vec, 0, size, 0);
+}
+
+/// Sorts strings with 3-way quick sort by one character at a time: it partitions by the d-th
+/// character of the partitioning string, and sorts the strings with equal d-th characters by the
+/// next character. Like the other quick sorts, it shuffles the strings first.
+pub fn three_way_string_quick_sort<Coll, Item>(vec: &mut Coll, rng: &mut ThreadRng)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: AsRef<[u8]> {
+    knuth_shuffle(vec, rng);
+
+    let size = vec.length();
+    three_way_string_sort(vec, 0, size, 0);
+}
+
+/// Sorts by keys less than radix, which are given for the elements in their current order, and
+/// returns the index that the elements of each key start at, followed by the size.
+fn sort_by_keys<Coll, Item>(vec: &mut Coll, radix: usize, keys: &[usize]) -> Vec<usize>
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item> {
+    // count the frequencies of the keys, and compute the start of each key from them
+    let mut count = vec![0; radix + 1];
+    for key in keys {
+        assert!(*key < radix, "key {} is not less than the radix {}", key, radix);
+        count[key + 1] += 1;
+    }
+    for r in 0..radix {
+        count[r + 1] += count[r];
+    }
+    let starts = count.clone();
+
+    // the index each element is moved to
+    let mut dest: Vec<usize> = keys.iter()
+        .map(|key| {
+            count[*key] += 1;
+            count[*key] - 1
+        })
+        .collect();
+
+    // move the elements, one cycle of the permutation at a time
+    for i in 0..dest.len() {
+        while dest[i] != i {
+            let d = dest[i];
+            vec.swap(i, d);
+            dest.swap(i, d);
+        }
+    }
+    starts
+}
+
+/// the d-th byte of the string plus 1, or 0 after the end of the string
+fn char_at(string: &[u8], d: usize) -> usize {
+    if d < string.len() { string[d] as usize + 1 } else { 0 }
+}
+
+/// insertion-sorts the strings from lo to (excluding) hi, which share their first d bytes
+fn insertion_sort_from<Coll, Item>(vec: &mut Coll, lo: usize, hi: usize, d: usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: AsRef<[u8]> {
+    insertion_sort_by_less(&mut SubRange::new(vec, lo, hi),
+                           &mut |a: &Item, b: &Item| a.as_ref()[d..] < b.as_ref()[d..]);
+}
+
+/// sorts the strings from lo to (excluding) hi, which share their first d bytes
+fn msd_sort_range<Coll, Item>(vec: &mut Coll, lo: usize, hi: usize, d: usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: AsRef<[u8]> {
+    if hi - lo <= CUTOFF {
+        insertion_sort_from(vec, lo, hi, d);
+        return;
+    }
+
+    let starts = {
+        let mut sub_range = SubRange::new(vec, lo, hi);
+        let keys: Vec<usize> = (0..hi - lo).map(|i| char_at(sub_range[i].as_ref(), d)).collect();
+        sort_by_keys(&mut sub_range, RADIX + 1, &keys)
+    };
+
+    // the strings that end at d are equal, and sorted already
+    for r in 1..RADIX + 1 {
+        msd_sort_range(vec, lo + starts[r], lo + starts[r + 1], d + 1);
+    }
+}
+
+/// sorts the strings from lo to (excluding) hi, which share their first d bytes
+fn three_way_string_sort<Coll, Item>(vec: &mut Coll, lo: usize, hi: usize, d: usize)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: AsRef<[u8]> {
+    if hi - lo <= CUTOFF {
+        insertion_sort_from(vec, lo, hi, d);
+        return;
+    }
+
+    let v = char_at(vec[lo].as_ref(), d);
+    let mut lt = lo;
+    let mut i = lo + 1;
+    let mut gt = hi;
+    while gt > i {
+        let t = char_at(vec[i].as_ref(), d);
+        if t < v {
+            vec.swap(lt, i);
+            lt += 1;
+            i += 1;
+        } else if t > v {
+            vec.swap(i, gt - 1);
+            gt -= 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    three_way_string_sort(vec, lo, lt, d);
+    // the strings that end at d are equal, and sorted already
+    if v > 0 {
+        three_way_string_sort(vec, lt, gt, d + 1);
+    }
+    three_way_string_sort(vec, gt, hi, d);
+}
+
+#[cfg(test)]
+mod test {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng, ThreadRng};
+    use super::{key_indexed_counting_sort, lsd_sort, lsd_string_sort, msd_sort, radix_sort,
+                three_way_string_quick_sort, RadixKey};
+    use ::is_sorted::{is_sorted, sort_some, sort_some_slices};
+    use ::stability::sorts_stably;
+
+    fn random_strings(rng: &mut ThreadRng, size: usize, max_len: usize, alphabet: &[u8]) -> Vec<String> {
+        (0..size)
+            .map(|_| {
+                let len = rng.gen_range(0, max_len + 1);
+                (0..len).map(|_| alphabet[rng.gen_range(0, alphabet.len())] as char).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn radix_keys() {
+        assert_eq!(0x1234u16.byte(0), 0x12);
+        assert_eq!(0x1234u16.byte(1), 0x34);
+        assert_eq!((-1i8).byte(0), 0x7f);
+        assert_eq!(0i8.byte(0), 0x80);
+        assert_eq!(<u64 as RadixKey>::BYTES, 8);
+    }
+
+    #[test]
+    fn counting_sort_is_stable() {
+        let mut vec = vec![(2, 'a'), (0, 'b'), (1, 'c'), (2, 'd'), (0, 'e')];
+        key_indexed_counting_sort(&mut vec, 3, |pair| pair.0);
+        assert_eq!(vec, vec![(0, 'b'), (0, 'e'), (1, 'c'), (2, 'a'), (2, 'd')]);
+
+        assert!(sorts_stably(&mut thread_rng(), |vec| key_indexed_counting_sort(vec, 10, |indexed| indexed.key as usize)));
+    }
+
+    #[test]
+    #[should_panic(expected = "key 3 is not less than the radix 3")]
+    fn counting_sort_checks_keys() {
+        key_indexed_counting_sort(&mut vec![1, 3, 2], 3, |key| *key);
+    }
+
+    #[test]
+    fn radix_sort_some() {
+        let mut rng = thread_rng();
+        sort_some::<u32>(&mut rng, radix_sort);
+        sort_some::<u64>(&mut rng, radix_sort);
+        sort_some::<i32>(&mut rng, radix_sort);
+        sort_some_slices::<u16>(&mut rng, radix_sort);
+        sort_some_slices::<i8>(&mut rng, radix_sort);
+
+        let mut array = [i64::MIN, 3, -3, 0, i64::MAX, -1];
+        radix_sort(&mut array);
+        assert_eq!(array, [i64::MIN, -3, -1, 0, 3, i64::MAX]);
+    }
+
+    #[test]
+    fn lsd_sort_decimal_digits() {
+        let mut vec = vec![329, 457, 657, 839, 436, 720, 355];
+        lsd_sort(&mut vec, 3, 10, |key, d| (key / 10usize.pow(2 - d as u32)) % 10);
+        assert_eq!(vec, vec![329, 355, 436, 457, 657, 720, 839]);
+    }
+
+    #[test]
+    fn lsd_string_sort_plates() {
+        let mut vec = vec!["4PGC938", "2IYE230", "3CIO720", "1ICK750", "1OHV845", "4JZY524", "1ICK750",
+                           "3CIO720", "1OHV845", "1OHV845", "2RLA629", "2RLA629", "3ATW723"];
+        let mut expected = vec.clone();
+        expected.sort();
+        lsd_string_sort(&mut vec, 7);
+        assert_eq!(vec, expected);
+
+        // only the first 2 characters count, and the sort is stable
+        let mut vec = vec!["abz", "aba", "aaz", "abb"];
+        lsd_string_sort(&mut vec, 2);
+        assert_eq!(vec, vec!["aaz", "abz", "aba", "abb"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn lsd_string_sort_short_string() {
+        lsd_string_sort(&mut vec!["abc", "ab"], 3);
+    }
+
+    #[test]
+    fn string_sorts_some() {
+        let mut rng = thread_rng();
+        for alphabet in &[&b"ab"[..], &b"acgt"[..], &b"abcdefghijklmnopqrstuvwxyz"[..]] {
+            for size in (0..30).map(|x| x * x) {
+                let strings = random_strings(&mut rng, size, 12, alphabet);
+                let mut expected = strings.clone();
+                expected.sort();
+
+                let mut vec = strings.clone();
+                msd_sort(&mut vec);
+                assert_eq!(vec, expected);
+
+                let mut vec = strings.clone();
+                three_way_string_quick_sort(&mut vec, &mut rng);
+                assert_eq!(vec, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn string_sorts_slices_of_str() {
+        let mut rng = thread_rng();
+        let mut vec = vec!["", "she", "sells", "seashells", "by", "the", "sea", "shore", "the", "shells",
+                           "she", "sells", "are", "surely", "seashells", "", "ünicode", "unicode"];
+        three_way_string_quick_sort(&mut vec[2..], &mut rng);
+        assert_eq!(vec[..2], ["", "she"]);
+        assert!(is_sorted(&vec[2..]));
+
+        msd_sort(&mut vec[..]);
+        assert!(is_sorted(&vec));
+        assert_eq!(vec[vec.len() - 1], "ünicode");
+    }
+}
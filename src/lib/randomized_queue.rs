@@ -0,0 +1,210 @@
+//! The randomized queue of Programming Assignment 2 of the course: a collection, from which items
+//! are removed uniformly at random.
+//!
+//! The items are kept in a resizing array. Dequeuing swaps a random item with the last one, and
+//! removes it from the end, in constant amortized time. Each iterator shuffles the indexes of the
+//! items with [`knuth_shuffle`], so that iterators are independent of each other, and each
+//! returns the items in a uniformly random order.
+//!
+//! [`knuth_shuffle`]: ../knuth_shuffle/fn.knuth_shuffle.html
+//!
+//! # Example
+//! ```
+//! use algorithms::randomized_queue::RandomizedQueue;
+//!
+//! let mut queue: RandomizedQueue<u32> = (0..5).collect();
+//! assert!(*queue.sample().unwrap() < 5);
+//!
+//! let mut items: Vec<u32> = queue.iter().cloned().collect();
+//! items.sort();
+//! assert_eq!(items, vec![0, 1, 2, 3, 4]);
+//!
+//! let item = queue.dequeue().unwrap();
+//! assert!(item < 5);
+//! assert_eq!(queue.size(), 4);
+//! ```
+
+extern crate rand;
+
+use self::rand::{thread_rng, Rng};
+use std::iter::FromIterator;
+use std::vec;
+use ::knuth_shuffle::knuth_shuffle;
+
+/// a queue, that dequeues its items uniformly at random
+#[derive(Clone, Debug)]
+pub struct RandomizedQueue<Item> {
+    vec: Vec<Item>,
+}
+
+impl<Item> RandomizedQueue<Item> {
+    pub fn new() -> RandomizedQueue<Item> {
+        RandomizedQueue { vec: Vec::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn enqueue(&mut self, item: Item) {
+        self.vec.push(item);
+    }
+
+    /// removes a random item, if the queue is not empty
+    pub fn dequeue(&mut self) -> Option<Item> {
+        if self.vec.is_empty() {
+            return None;
+        }
+        let index = thread_rng().gen_range(0, self.vec.len());
+        Some(self.vec.swap_remove(index))
+    }
+
+    /// a random item, without removing it, if the queue is not empty
+    pub fn sample(&self) -> Option<&Item> {
+        if self.vec.is_empty() {
+            return None;
+        }
+        self.vec.get(thread_rng().gen_range(0, self.vec.len()))
+    }
+
+    /// iterates over the items in a random order, which is independent of other iterators
+    pub fn iter(&self) -> RandomizedQueueIter<'_, Item> {
+        let mut order: Vec<usize> = (0..self.vec.len()).collect();
+        knuth_shuffle(&mut order, &mut thread_rng());
+        RandomizedQueueIter {
+            vec: &self.vec,
+            order: order.into_iter(),
+        }
+    }
+}
+
+impl<Item> Default for RandomizedQueue<Item> {
+    fn default() -> RandomizedQueue<Item> {
+        RandomizedQueue::new()
+    }
+}
+
+impl<Item> FromIterator<Item> for RandomizedQueue<Item> {
+    fn from_iter<I>(iter: I) -> RandomizedQueue<Item>
+        where I: IntoIterator<Item=Item> {
+        RandomizedQueue { vec: iter.into_iter().collect() }
+    }
+}
+
+impl<Item> Extend<Item> for RandomizedQueue<Item> {
+    fn extend<I>(&mut self, iter: I)
+        where I: IntoIterator<Item=Item> {
+        self.vec.extend(iter);
+    }
+}
+
+impl<Item> IntoIterator for RandomizedQueue<Item> {
+    type Item = Item;
+    type IntoIter = vec::IntoIter<Item>;
+
+    /// shuffles the items, and iterates over them
+    fn into_iter(mut self) -> vec::IntoIter<Item> {
+        knuth_shuffle(&mut self.vec, &mut thread_rng());
+        self.vec.into_iter()
+    }
+}
+
+impl<'a, Item> IntoIterator for &'a RandomizedQueue<Item> {
+    type Item = &'a Item;
+    type IntoIter = RandomizedQueueIter<'a, Item>;
+
+    fn into_iter(self) -> RandomizedQueueIter<'a, Item> {
+        self.iter()
+    }
+}
+
+/// iterates over the items of a randomized queue, in a shuffled order of their indexes
+pub struct RandomizedQueueIter<'a, Item>
+    where Item: 'a {
+    vec: &'a [Item],
+    order: vec::IntoIter<usize>,
+}
+
+impl<'a, Item> Iterator for RandomizedQueueIter<'a, Item> {
+    type Item = &'a Item;
+
+    fn next(&mut self) -> Option<&'a Item> {
+        self.order.next().map(|index| &self.vec[index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<'a, Item> ExactSizeIterator for RandomizedQueueIter<'a, Item> {}
+
+#[cfg(test)]
+mod tests {
+    use super::RandomizedQueue;
+
+    #[test]
+    fn enqueue_and_dequeue_all() {
+        let mut queue = RandomizedQueue::new();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), None);
+        assert_eq!(queue.sample(), None);
+
+        for item in 0..100 {
+            queue.enqueue(item);
+        }
+        assert_eq!(queue.size(), 100);
+        let mut items = vec![];
+        while let Some(item) = queue.dequeue() {
+            items.push(item);
+        }
+        assert_ne!(items, (0..100).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..100).collect::<Vec<_>>());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn dequeue_is_uniform() {
+        // dequeues one of 4 items, 40000 times. Each should come out about 10000 times, which a
+        // chi-squared statistic with 3 degrees of freedom above 30.66 rejects at the 0.0001 % level.
+        let mut counts = [0.0; 4];
+        for _ in 0..40000 {
+            let mut queue: RandomizedQueue<usize> = (0..4).collect();
+            counts[queue.dequeue().unwrap()] += 1.0;
+        }
+        let chi_squared: f64 = counts.iter().map(|count| (count - 10000.0) * (count - 10000.0) / 10000.0).sum();
+        assert!(chi_squared < 30.66, "counts {:?}", counts);
+    }
+
+    #[test]
+    fn sample_leaves_the_item() {
+        let queue: RandomizedQueue<u32> = (0..10).collect();
+        for _ in 0..100 {
+            assert!(*queue.sample().unwrap() < 10);
+        }
+        assert_eq!(queue.size(), 10);
+    }
+
+    #[test]
+    fn iterators_are_independent() {
+        let queue: RandomizedQueue<u32> = (0..20).collect();
+        let mut first = queue.iter();
+        let mut second = queue.iter();
+        assert_eq!(first.len(), 20);
+        let a: Vec<u32> = first.by_ref().take(10).cloned().collect();
+        let b: Vec<u32> = second.by_ref().cloned().collect();
+        let rest: Vec<u32> = first.cloned().collect();
+        // the chance that two shuffles of 20 items are the same is 1 in 20!
+        assert_ne!([a.clone(), rest.clone()].concat(), b);
+
+        for mut items in [[a, rest].concat(), b, queue.into_iter().collect()] {
+            items.sort();
+            assert_eq!(items, (0..20).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! A double-ended queue in a circular array: the items are stored from the head of the array on,
+//! and wrap around at its end, so items are added and removed at both ends without moving the
+//! others. Unlike the linked [`Deque`], it does not allocate a node per item.
+//!
+//! The array is resized like the resizing array of the course: it doubles when it is full, and it
+//! halves when it is only a quarter full, so that each operation takes constant amortized time,
+//! and the array is never more than four times as large as the items it holds.
+//!
+//! [`Deque`]: ../deque/struct.Deque.html
+//!
+//! # Example
+//! ```
+//! use algorithms::deque::DequeT;
+//! use algorithms::ring_deque::RingDeque;
+//!
+//! let mut deque = RingDeque::new();
+//! for item in 1..6 {
+//!     deque.add_last(item);
+//! }
+//! deque.add_first(0);
+//! assert_eq!(deque.capacity(), 8);
+//!
+//! assert_eq!(deque.remove_first(), Some(0));
+//! assert_eq!(deque.remove_last(), Some(5));
+//! assert_eq!(deque.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+//! ```
+
+use std::fmt;
+use std::iter::FromIterator;
+use ::deque::DequeT;
+
+/// a double-ended queue in a circular array, that resizes by doubling and halving
+pub struct RingDeque<Item> {
+    // the slots of the array, of which size are occupied, from head on
+    buf: Vec<Option<Item>>,
+    head: usize,
+    size: usize,
+}
+
+impl<Item> RingDeque<Item> {
+    pub fn new() -> RingDeque<Item> {
+        RingDeque {
+            buf: Vec::new(),
+            head: 0,
+            size: 0,
+        }
+    }
+
+    /// the number of items the array can hold before it doubles
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn peek_first(&self) -> Option<&Item> {
+        self.get(0)
+    }
+
+    pub fn peek_last(&self) -> Option<&Item> {
+        self.size.checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// the item at the index, counted from the first one, in constant time
+    pub fn get(&self, index: usize) -> Option<&Item> {
+        if index < self.size {
+            self.buf[self.slot(index)].as_ref()
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> RingDequeIter<'_, Item> {
+        RingDequeIter {
+            deque: self,
+            front: 0,
+            back: self.size,
+        }
+    }
+
+    // the slot of the array that holds the item at the index
+    fn slot(&self, index: usize) -> usize {
+        let slot = self.head + index;
+        if slot < self.buf.len() { slot } else { slot - self.buf.len() }
+    }
+
+    // moves the items to a new array of the capacity, from its start on
+    fn resize(&mut self, capacity: usize) {
+        let mut buf: Vec<Option<Item>> = Vec::with_capacity(capacity);
+        for index in 0..self.size {
+            let slot = self.slot(index);
+            buf.push(self.buf[slot].take());
+        }
+        buf.resize_with(capacity, || None);
+        self.buf = buf;
+        self.head = 0;
+    }
+
+    fn grow_if_full(&mut self) {
+        if self.size == self.buf.len() {
+            let capacity = (2 * self.buf.len()).max(1);
+            self.resize(capacity);
+        }
+    }
+
+    fn shrink_if_quarter_full(&mut self) {
+        if self.size > 0 && self.size == self.buf.len() / 4 {
+            let capacity = self.buf.len() / 2;
+            self.resize(capacity);
+        }
+    }
+}
+
+impl<Item> DequeT for RingDeque<Item> {
+    type Item = Item;
+
+    fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add_first(&mut self, item: Item) {
+        self.grow_if_full();
+        self.head = if self.head == 0 { self.buf.len() - 1 } else { self.head - 1 };
+        self.buf[self.head] = Some(item);
+        self.size += 1;
+    }
+
+    fn add_last(&mut self, item: Item) {
+        self.grow_if_full();
+        let slot = self.slot(self.size);
+        self.buf[slot] = Some(item);
+        self.size += 1;
+    }
+
+    fn remove_first(&mut self) -> Option<Item> {
+        if self.size == 0 {
+            return None;
+        }
+        let item = self.buf[self.head].take();
+        self.head = self.slot(1);
+        self.size -= 1;
+        self.shrink_if_quarter_full();
+        item
+    }
+
+    fn remove_last(&mut self) -> Option<Item> {
+        if self.size == 0 {
+            return None;
+        }
+        let slot = self.slot(self.size - 1);
+        let item = self.buf[slot].take();
+        self.size -= 1;
+        self.shrink_if_quarter_full();
+        item
+    }
+}
+
+impl<Item> Default for RingDeque<Item> {
+    fn default() -> RingDeque<Item> {
+        RingDeque::new()
+    }
+}
+
+impl<Item> fmt::Debug for RingDeque<Item>
+    where Item: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<Item> FromIterator<Item> for RingDeque<Item> {
+    fn from_iter<I>(iter: I) -> RingDeque<Item>
+        where I: IntoIterator<Item=Item> {
+        let mut deque = RingDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<Item> Extend<Item> for RingDeque<Item> {
+    fn extend<I>(&mut self, iter: I)
+        where I: IntoIterator<Item=Item> {
+        for item in iter {
+            self.add_last(item);
+        }
+    }
+}
+
+/// iterates over the items of a ring deque from both ends
+pub struct RingDequeIter<'a, Item>
+    where Item: 'a {
+    deque: &'a RingDeque<Item>,
+    // the indexes of the items left, from front until back
+    front: usize,
+    back: usize,
+}
+
+impl<'a, Item> Iterator for RingDequeIter<'a, Item> {
+    type Item = &'a Item;
+
+    fn next(&mut self) -> Option<&'a Item> {
+        if self.front < self.back {
+            self.front += 1;
+            self.deque.get(self.front - 1)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+
+impl<'a, Item> DoubleEndedIterator for RingDequeIter<'a, Item> {
+    fn next_back(&mut self) -> Option<&'a Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            self.deque.get(self.back)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, Item> ExactSizeIterator for RingDequeIter<'a, Item> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
+    use std::collections::VecDeque;
+    use ::deque::DequeT;
+    use super::RingDeque;
+
+    #[test]
+    fn operations_like_a_vec_deque() {
+        let mut rng = thread_rng();
+        let mut deque = RingDeque::new();
+        let mut oracle: VecDeque<u32> = VecDeque::new();
+        for item in 0..5000 {
+            // more adds than removes in the first half, and the other way around in the second
+            let add = rng.gen_range(0, 10) < if item < 2500 { 6 } else { 4 };
+            match (add, rng.gen()) {
+                (true, true) => {
+                    deque.add_first(item);
+                    oracle.push_front(item);
+                }
+                (true, false) => {
+                    deque.add_last(item);
+                    oracle.push_back(item);
+                }
+                (false, true) => assert_eq!(deque.remove_first(), oracle.pop_front()),
+                (false, false) => assert_eq!(deque.remove_last(), oracle.pop_back()),
+            }
+            assert_eq!(deque.size(), oracle.len());
+            assert_eq!(deque.peek_first(), oracle.front());
+            assert_eq!(deque.peek_last(), oracle.back());
+            assert!(deque.capacity() >= deque.size());
+            assert!(deque.is_empty() || deque.capacity() <= 4 * deque.size());
+        }
+        assert!(deque.iter().eq(oracle.iter()));
+        assert!(deque.iter().rev().eq(oracle.iter().rev()));
+    }
+
+    #[test]
+    fn resizes_by_doubling_and_halving() {
+        let mut deque = RingDeque::new();
+        assert_eq!(deque.capacity(), 0);
+        let mut capacities = vec![];
+        for item in 0..9 {
+            deque.add_last(item);
+            capacities.push(deque.capacity());
+        }
+        assert_eq!(capacities, vec![1, 2, 4, 4, 8, 8, 8, 8, 16]);
+
+        capacities.clear();
+        while deque.remove_first().is_some() {
+            capacities.push(deque.capacity());
+        }
+        assert_eq!(capacities, vec![16, 16, 16, 16, 8, 8, 4, 2, 2]);
+    }
+
+    #[test]
+    fn wraps_around() {
+        let mut deque: RingDeque<u32> = (0..4).collect();
+        deque.remove_first();
+        deque.remove_first();
+        deque.add_last(4);
+        deque.add_last(5);
+        assert_eq!(deque.capacity(), 4);
+        assert_eq!(deque.get(3), Some(&5));
+        assert_eq!(deque.get(4), None);
+        assert_eq!(format!("{:?}", deque), "[2, 3, 4, 5]");
+
+        // doubling unwraps the items to the start of the new array
+        deque.add_first(1);
+        assert_eq!(deque.capacity(), 8);
+        assert_eq!(format!("{:?}", deque), "[1, 2, 3, 4, 5]");
+    }
+}
@@ -0,0 +1,454 @@
+//! Selection and partial sorting: finding the kth smallest elements of a collection, without
+//! sorting all of it.
+//!
+//! * [`select_nth_unstable`] partitions a slice around its kth smallest element, like
+//!   [`quick_select`], and returns references into the partitioned slice
+//! * [`partial_sort`] sorts only the k smallest elements, to the front of a collection
+//! * [`top_k`] keeps the k smallest elements of a stream in a bounded [`MaxPQ`]
+//! * [`median`] and [`percentile`] select by rank
+//! * [`linear_select`] selects with the median of medians as pivot, in linear time also in the
+//!   worst case
+//!
+//! [`select_nth_unstable`]: fn.select_nth_unstable.html
+//! [`quick_select`]: ../quick_sort/fn.quick_select.html
+//! [`partial_sort`]: fn.partial_sort.html
+//! [`top_k`]: fn.top_k.html
+//! [`MaxPQ`]: ../max_pq/trait.MaxPQ.html
+//! [`median`]: fn.median.html
+//! [`percentile`]: fn.percentile.html
+//! [`linear_select`]: fn.linear_select.html
+//!
+//! # Example
+//! ```
+//! extern crate algorithms;
+//! extern crate rand;
+//!
+//! use algorithms::select::{median, partial_sort, top_k};
+//!
+//! # fn main() {
+//! let mut rng = rand::thread_rng();
+//!
+//! let mut vec = vec![7, 3, 9, 1, 5, 8, 2];
+//! assert_eq!(*median(&mut vec, &mut rng), 5);
+//!
+//! partial_sort(&mut vec, 3, &mut rng);
+//! assert_eq!(vec[..3], [1, 2, 3]);
+//!
+//! assert_eq!(top_k(vec![7, 3, 9, 1, 5, 8, 2], 2), vec![1, 2]);
+//! # }
+//! ```
+
+extern crate rand;
+
+use self::rand::ThreadRng;
+use std::cmp::{min, Ordering};
+use std::ops::{Index, IndexMut};
+use ::index_ops::{Length, Swap};
+use ::insertion_sort::insertion_sort_by_less;
+use ::max_pq::{heap_sort, MaxPQ};
+use ::quick_sort::{intro_sort_range, lg, select_by_less, three_way_partition_by_less};
+use ::sub_range::SubRange;
+
+/// the size of the groups, whose medians [`linear_select`] takes the median of
+///
+/// [`linear_select`]: fn.linear_select.html
+const GROUP_SIZE: usize = 5;
+
+/// Partitions the slice like [`quick_select`], so that its kth smallest element is at index k, and
+/// returns the elements before index k, the kth smallest element, and the elements after it.
+/// Like the partitioning of quick sort, it is not stable.
+///
+/// # Panics
+/// Panics if k is not less than the length of the slice.
+///
+/// [`quick_select`]: ../quick_sort/fn.quick_select.html
+pub fn select_nth_unstable<'a, Item>(vec: &'a mut [Item], k: usize, rng: &mut ThreadRng) -> (&'a mut [Item], &'a mut Item, &'a mut [Item])
+    where Item: PartialOrd {
+    select_by_less(vec, k, rng, &mut |a: &Item, b: &Item| a < b);
+    split_at_nth(vec, k)
+}
+
+/// partitions like [`select_nth_unstable`], in the order defined by the compare function
+///
+/// [`select_nth_unstable`]: fn.select_nth_unstable.html
+pub fn select_nth_unstable_by<'a, Item, F>(vec: &'a mut [Item], k: usize, rng: &mut ThreadRng, mut compare: F) -> (&'a mut [Item], &'a mut Item, &'a mut [Item])
+    where F: FnMut(&Item, &Item) -> Ordering {
+    select_by_less(vec, k, rng, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+    split_at_nth(vec, k)
+}
+
+/// partitions like [`select_nth_unstable`], in the order of the keys that the key function extracts
+///
+/// [`select_nth_unstable`]: fn.select_nth_unstable.html
+pub fn select_nth_unstable_by_key<'a, Item, K, F>(vec: &'a mut [Item], k: usize, rng: &mut ThreadRng, mut key: F) -> (&'a mut [Item], &'a mut Item, &'a mut [Item])
+    where K: Ord,
+          F: FnMut(&Item) -> K {
+    select_by_less(vec, k, rng, &mut |a: &Item, b: &Item| key(a) < key(b));
+    split_at_nth(vec, k)
+}
+
+fn split_at_nth<Item>(vec: &mut [Item], k: usize) -> (&mut [Item], &mut Item, &mut [Item]) {
+    let (before, rest) = vec.split_at_mut(k);
+    let (nth, after) = rest.split_first_mut().unwrap();
+    (before, nth, after)
+}
+
+/// Sorts the k smallest elements of a collection to its front: it selects the kth smallest element
+/// like [`quick_select`], and sorts the elements before it like [`intro_sort`]. The order of the
+/// other elements is unspecified. It takes time proportional to N + k lg k.
+///
+/// If k is not less than the length of the collection, all of it is sorted.
+///
+/// [`quick_select`]: ../quick_sort/fn.quick_select.html
+/// [`intro_sort`]: ../quick_sort/fn.intro_sort.html
+pub fn partial_sort<Coll, Item>(vec: &mut Coll, k: usize, rng: &mut ThreadRng)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    partial_sort_by_less(vec, k, rng, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// sorts like [`partial_sort`], in the order defined by the compare function
+///
+/// [`partial_sort`]: fn.partial_sort.html
+pub fn partial_sort_by<Coll, Item, F>(vec: &mut Coll, k: usize, rng: &mut ThreadRng, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    partial_sort_by_less(vec, k, rng, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// sorts like [`partial_sort`], in the order of the keys that the key function extracts
+///
+/// [`partial_sort`]: fn.partial_sort.html
+pub fn partial_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, k: usize, rng: &mut ThreadRng, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    partial_sort_by_less(vec, k, rng, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn partial_sort_by_less<Coll, Item, F>(vec: &mut Coll, k: usize, rng: &mut ThreadRng, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
+    let k = min(k, size);
+    if k < size {
+        select_by_less(vec, k, rng, less);
+    }
+    intro_sort_range(vec, 0, k, 2 * lg(k), less);
+}
+
+/// Returns the k smallest items of an iterator, in ascending order. It keeps them in a [`MaxPQ`]
+/// of at most k + 1 items, deleting the largest one after each insert, so it takes time
+/// proportional to N lg k, and space proportional to k.
+///
+/// For the k largest items, wrap them in [`Reverse`].
+///
+/// [`MaxPQ`]: ../max_pq/trait.MaxPQ.html
+/// [`Reverse`]: https://doc.rust-lang.org/std/cmp/struct.Reverse.html
+pub fn top_k<I, Item>(iter: I, k: usize) -> Vec<Item>
+    where I: IntoIterator<Item=Item>,
+          Item: PartialOrd {
+    let iter = iter.into_iter();
+    // no more than the items of the iterator, so a huge k does not allocate up front
+    let mut pq: Vec<Item> = Vec::with_capacity(k.min(iter.size_hint().0).saturating_add(1));
+    if k == 0 {
+        return pq;
+    }
+    for item in iter {
+        pq.heap_insert(item);
+        if pq.size() > k {
+            pq.del_max();
+        }
+    }
+    heap_sort(&mut pq);
+    pq
+}
+
+/// Selects the median of a collection with [`quick_select`]. For an even number of elements, it is
+/// the lower one of the two middle elements.
+///
+/// # Panics
+/// Panics if the collection is empty.
+///
+/// [`quick_select`]: ../quick_sort/fn.quick_select.html
+pub fn median<'a, Coll, Item>(vec: &'a mut Coll, rng: &mut ThreadRng) -> &'a Item
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    assert!(vec.length() > 0, "the median of an empty collection is undefined");
+    let k = (vec.length() - 1) / 2;
+    select_by_less(vec, k, rng, &mut |a: &Item, b: &Item| a < b);
+    &vec[k]
+}
+
+/// Selects the pth percentile of a collection with [`quick_select`], by the nearest-rank method:
+/// the smallest element, that is not less than p percent of the elements. The 0th percentile is
+/// the smallest element.
+///
+/// # Panics
+/// Panics if the collection is empty, or if p is not between 0 and 100.
+///
+/// [`quick_select`]: ../quick_sort/fn.quick_select.html
+pub fn percentile<'a, Coll, Item>(vec: &'a mut Coll, p: f64, rng: &mut ThreadRng) -> &'a Item
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    assert!(vec.length() > 0, "the percentile of an empty collection is undefined");
+    assert!((0.0..=100.0).contains(&p), "the percentile {} is not between 0 and 100", p);
+    let rank = (p / 100.0 * vec.length() as f64).ceil() as usize;
+    let k = rank.max(1) - 1;
+    select_by_less(vec, k, rng, &mut |a: &Item, b: &Item| a < b);
+    &vec[k]
+}
+
+/// Selects the kth smallest element in a collection, counting from 0, like [`quick_select`], but
+/// with the median of medians as partitioning element: the median of the medians of groups of 5
+/// elements, which it selects recursively. At least 3/10 of the elements are not greater than it,
+/// and at least 3/10 are not less, so it takes linear time also in the worst case. It needs no
+/// random numbers, but is slower than quick select on average.
+///
+/// # Panics
+/// Panics if k is not less than the length of the collection.
+///
+/// [`quick_select`]: ../quick_sort/fn.quick_select.html
+pub fn linear_select<Coll, Item>(vec: &mut Coll, k: usize) -> &Item
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    linear_select_by_less(vec, k, &mut |a: &Item, b: &Item| a < b)
+}
+
+/// selects like [`linear_select`], in the order defined by the compare function
+///
+/// [`linear_select`]: fn.linear_select.html
+pub fn linear_select_by<Coll, Item, F>(vec: &mut Coll, k: usize, mut compare: F) -> &Item
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    linear_select_by_less(vec, k, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less)
+}
+
+/// selects like [`linear_select`], in the order of the keys that the key function extracts
+///
+/// [`linear_select`]: fn.linear_select.html
+pub fn linear_select_by_key<Coll, Item, K, F>(vec: &mut Coll, k: usize, mut key: F) -> &Item
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    linear_select_by_less(vec, k, &mut |a: &Item, b: &Item| key(a) < key(b))
+}
+
+fn linear_select_by_less<'a, Coll, Item, F>(vec: &'a mut Coll, k: usize, less: &mut F) -> &'a Item
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
+    assert!(k < size, "index {} is out of range for a collection of length {}", k, size);
+    median_of_medians_select(vec, 0, size, k, less);
+    &vec[k]
+}
+
+// partitions the range from lo to (excluding) hi, until its kth smallest element is at index k
+fn median_of_medians_select<Coll, Item, F>(vec: &mut Coll, mut lo: usize, mut hi: usize, k: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
+    loop {
+        if hi - lo <= GROUP_SIZE {
+            insertion_sort_by_less(&mut SubRange::new(vec, lo, hi), less);
+            return;
+        }
+
+        // move the median of each group to the front of the range. The group at g starts at or
+        // after lo + medians, so only elements of groups already visited are displaced.
+        let mut medians = 0;
+        for g in (lo..hi).step_by(GROUP_SIZE) {
+            let end = min(g + GROUP_SIZE, hi);
+            insertion_sort_by_less(&mut SubRange::new(vec, g, end), less);
+            vec.swap(lo + medians, g + (end - g - 1) / 2);
+            medians += 1;
+        }
+
+        let mid = lo + (medians - 1) / 2;
+        median_of_medians_select(vec, lo, lo + medians, mid, less);
+        vec.swap(lo, mid);
+
+        let (lt, gt) = three_way_partition_by_less(vec, lo, hi, less);
+        if k < lt {
+            hi = lt;
+        } else if k >= gt {
+            lo = gt;
+        } else {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::{thread_rng, Rng};
+    use std::cmp::Reverse;
+    use super::{select_nth_unstable, select_nth_unstable_by, select_nth_unstable_by_key};
+    use super::{partial_sort, partial_sort_by, partial_sort_by_key, top_k, median, percentile};
+    use super::{linear_select, linear_select_by, linear_select_by_key};
+
+    /// whether the element at index k is in its sorted position, with no greater element before it
+    /// and no smaller one after it
+    fn is_partitioned_at(vec: &[u32], k: usize) -> bool {
+        let mut sorted = vec.to_vec();
+        sorted.sort();
+        vec[k] == sorted[k] && vec[..k].iter().all(|x| *x <= vec[k]) && vec[k + 1..].iter().all(|x| *x >= vec[k])
+    }
+
+    fn random_vecs() -> Vec<Vec<u32>> {
+        let mut rng = thread_rng();
+        (1..20).map(|x| x * x)
+            .flat_map(|size| vec![
+                (0..size).map(|_| rng.gen()).collect(),
+                (0..size).map(|_| rng.gen_range(0, 5)).collect(),
+                (0..size as u32).collect(),
+                (0..size as u32).rev().collect(),
+            ])
+            .collect()
+    }
+
+    #[test]
+    fn select_nth_unstable_some() {
+        let mut rng = thread_rng();
+        for mut vec in random_vecs() {
+            let k = rng.gen_range(0, vec.len());
+            let kth = {
+                let (before, nth, after) = select_nth_unstable(&mut vec, k, &mut rng);
+                assert_eq!(before.len(), k);
+                assert!(before.iter().all(|x| x <= nth) && after.iter().all(|x| x >= nth));
+                *nth
+            };
+            assert_eq!(vec[k], kth);
+            assert!(is_partitioned_at(&vec, k));
+        }
+    }
+
+    #[test]
+    fn select_nth_unstable_returns_mutable_references() {
+        let mut vec = vec![5, 1, 4, 2, 3];
+        {
+            let (before, nth, _) = select_nth_unstable(&mut vec, 2, &mut thread_rng());
+            *nth = 10;
+            before.sort();
+        }
+        assert_eq!(vec[..3], [1, 2, 10]);
+    }
+
+    #[test]
+    fn select_nth_unstable_by_some() {
+        let mut rng = thread_rng();
+        let mut vec = vec![(3, 'c'), (1, 'a'), (4, 'd'), (2, 'b'), (5, 'e')];
+        assert_eq!(*select_nth_unstable_by(&mut vec, 0, &mut rng, |a, b| b.0.cmp(&a.0)).1, (5, 'e'));
+        assert_eq!(*select_nth_unstable_by_key(&mut vec, 1, &mut rng, |pair| pair.1).1, (2, 'b'));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 0 is out of range for a collection of length 0")]
+    fn select_nth_unstable_empty() {
+        let mut vec: Vec<u32> = vec![];
+        select_nth_unstable(&mut vec, 0, &mut thread_rng());
+    }
+
+    #[test]
+    fn partial_sort_some() {
+        let mut rng = thread_rng();
+        for mut vec in random_vecs() {
+            let size = vec.len();
+            let k = rng.gen_range(0, size + 2);
+            let mut sorted = vec.clone();
+            sorted.sort();
+            partial_sort(&mut vec, k, &mut rng);
+
+            let k = k.min(size);
+            assert_eq!(vec[..k], sorted[..k]);
+            vec.sort();
+            assert_eq!(vec, sorted);
+        }
+    }
+
+    #[test]
+    fn partial_sort_by_some() {
+        let mut rng = thread_rng();
+        let mut vec = vec![3, 8, 1, 9, 4, 7];
+        partial_sort_by(&mut vec, 2, &mut rng, |a, b| b.cmp(a));
+        assert_eq!(vec[..2], [9, 8]);
+
+        let mut pairs = vec![(1, 'd'), (2, 'b'), (3, 'a'), (4, 'c')];
+        partial_sort_by_key(&mut pairs, 3, &mut rng, |pair| pair.1);
+        assert_eq!(pairs[..3], [(3, 'a'), (2, 'b'), (4, 'c')]);
+    }
+
+    #[test]
+    fn top_k_some() {
+        let mut rng = thread_rng();
+        for vec in random_vecs() {
+            let k = rng.gen_range(0, vec.len() + 2);
+            let mut sorted = vec.clone();
+            sorted.sort();
+            sorted.truncate(k);
+            assert_eq!(top_k(vec, k), sorted);
+        }
+
+        assert_eq!(top_k(vec![3, 8, 1, 9, 4], 0), vec![]);
+        let largest: Vec<u32> = top_k((0..100).map(Reverse), 3).into_iter().map(|r| r.0).collect();
+        assert_eq!(largest, vec![99, 98, 97]);
+        // a k larger than the items does not allocate for k
+        assert_eq!(top_k(0..10, 1 << 40), (0..10).collect::<Vec<_>>());
+        assert_eq!(top_k((0..10).filter(|x| x % 2 == 0), usize::MAX), vec![0, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn median_and_percentile() {
+        let mut rng = thread_rng();
+        assert_eq!(*median(&mut vec![7], &mut rng), 7);
+        assert_eq!(*median(&mut vec![7, 3, 9, 1, 5], &mut rng), 5);
+        assert_eq!(*median(&mut vec![7, 3, 9, 1, 5, 8], &mut rng), 5);
+
+        let mut vec: Vec<u32> = (1..101).rev().collect();
+        assert_eq!(*percentile(&mut vec, 0.0, &mut rng), 1);
+        assert_eq!(*percentile(&mut vec, 25.0, &mut rng), 25);
+        assert_eq!(*percentile(&mut vec, 99.5, &mut rng), 100);
+        assert_eq!(*percentile(&mut vec, 100.0, &mut rng), 100);
+
+        let mut vec = vec![15, 20, 35, 40, 50];
+        assert_eq!(*percentile(&mut vec, 30.0, &mut rng), 20);
+        assert_eq!(*percentile(&mut vec, 40.0, &mut rng), 20);
+        assert_eq!(*percentile(&mut vec, 50.0, &mut rng), 35);
+    }
+
+    #[test]
+    #[should_panic(expected = "the percentile 101 is not between 0 and 100")]
+    fn percentile_out_of_range() {
+        percentile(&mut vec![1, 2, 3], 101.0, &mut thread_rng());
+    }
+
+    #[test]
+    #[should_panic(expected = "the median of an empty collection is undefined")]
+    fn median_empty() {
+        let mut vec: Vec<u32> = vec![];
+        median(&mut vec, &mut thread_rng());
+    }
+
+    #[test]
+    fn linear_select_some() {
+        let mut rng = thread_rng();
+        for mut vec in random_vecs() {
+            let k = rng.gen_range(0, vec.len());
+            linear_select(&mut vec, k);
+            assert!(is_partitioned_at(&vec, k));
+        }
+
+        let mut vec = vec![(3, 'c'), (1, 'a'), (4, 'd'), (2, 'b'), (5, 'e'), (0, 'f'), (6, 'g')];
+        assert_eq!(*linear_select_by(&mut vec, 0, |a, b| b.0.cmp(&a.0)), (6, 'g'));
+        assert_eq!(*linear_select_by_key(&mut vec, 1, |pair| pair.1), (2, 'b'));
+    }
+
+    #[test]
+    fn linear_select_slices() {
+        let mut array = [9, 3, 7, 1, 8, 2, 6, 4, 5, 0, 11, 10];
+        assert_eq!(*linear_select(&mut array[2..10], 3), 4);
+        assert_eq!(array[..2], [9, 3]);
+        assert_eq!(array[10..], [11, 10]);
+    }
+}
@@ -8,24 +8,54 @@
 //! assert_eq!(vec, vec![1,2,3,4,5]);
 //! ```
 
+use std::cmp::Ordering;
 use std::ops::{Index, IndexMut};
 use ::index_ops::{Length, Swap};
 
 /// Sort types that implement [`Index`],  [`IndexMut`], [`Length`] and [`Swap`] with the selection sort algorithm.
 ///
+/// Selection sort is not stable: exchanging the minimum with the first unsorted element can move
+/// that element behind an element with an equal key.
+///
 /// [`Index`]: https://doc.rust-lang.org/std/ops/trait.Index.html
 /// [`IndexMut`]: https://doc.rust-lang.org/std/ops/trait.IndexMut.html
 /// [`Length`]: ../index_ops/trait.Length.html
 /// [`Swap`]: ../index_ops/trait.Swap.html
 pub fn selection_sort<Coll, Item>(vec: &mut Coll)
-    where Coll: Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
           Item: PartialOrd {
+    selection_sort_by_less(vec, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// Sorts like [`selection_sort`], in the order defined by the compare function.
+///
+/// [`selection_sort`]: fn.selection_sort.html
+pub fn selection_sort_by<Coll, Item, F>(vec: &mut Coll, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    selection_sort_by_less(vec, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts like [`selection_sort`], in the order of the keys that the key function extracts.
+///
+/// [`selection_sort`]: fn.selection_sort.html
+pub fn selection_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    selection_sort_by_less(vec, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+/// the selection sort algorithm, where less decides if one element is less than another
+fn selection_sort_by_less<Coll, Item, F>(vec: &mut Coll, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     let size = vec.length();
     if size > 1 {
         for i in 0..size - 1 {
             let mut min = i;
             for j in i..size {
-                if vec[j] < vec[min] {
+                if less(&vec[j], &vec[min]) {
                     min = j;
                 }
             }
@@ -39,8 +69,9 @@ mod test {
     extern crate rand;
 
     use self::rand::thread_rng;
-    use super::selection_sort;
-    use ::is_sorted::sort_some;
+    use super::{selection_sort, selection_sort_by, selection_sort_by_key};
+    use ::is_sorted::{sort_some, sort_some_by, sort_some_slices};
+    use ::stability::{indexed, is_stably_sorted, sorts_stably};
 
     #[test]
     fn vec_swap() {
@@ -54,4 +85,30 @@ mod test {
         let mut rng = thread_rng();
         sort_some::<u32>(&mut rng, selection_sort);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn selection_sort_slices() {
+        let mut rng = thread_rng();
+        sort_some_slices::<u32>(&mut rng, selection_sort);
+
+        let mut array = [3, 2, 4, 5, 1];
+        selection_sort(&mut array);
+        assert_eq!(array, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn selection_sort_by_some() {
+        let mut rng = thread_rng();
+        sort_some_by(&mut rng,
+                     |slice| selection_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| selection_sort_by_key(slice, |pair| pair.1));
+    }
+
+    #[test]
+    fn selection_sort_is_not_stable() {
+        let mut vec = indexed(vec![2, 2, 1]);
+        selection_sort(&mut vec);
+        assert!(!is_stably_sorted(&vec));
+        assert!(!sorts_stably(&mut thread_rng(), selection_sort));
+    }
+}
@@ -1,36 +1,229 @@
-use ::index_ops::Length;
-use ::insertion_sort::insertion_sort;
-use ::step::Step;
+//! Shell sort, with pluggable sequences of increments: the [`GapSequence`]s [`Knuth`],
+//! [`Sedgewick`], [`Tokuda`], [`Ciura`] and [`Pratt`].
+//!
+//! [`GapSequence`]: trait.GapSequence.html
+//! [`Knuth`]: struct.Knuth.html
+//! [`Sedgewick`]: struct.Sedgewick.html
+//! [`Tokuda`]: struct.Tokuda.html
+//! [`Ciura`]: struct.Ciura.html
+//! [`Pratt`]: struct.Pratt.html
+//!
+//! # Example
+//! ```
+//! use algorithms::shell_sort::{shell_sort_with, Ciura, GapSequence};
+//!
+//! assert_eq!(Ciura.gaps(100), vec![1, 4, 10, 23, 57]);
+//!
+//! let mut vec = vec![8, 1, 5, 3, 4, 7, 3, 6, 9, 7, 6, 5, 4, 2];
+//! shell_sort_with(&mut vec, &Ciura);
+//! assert_eq!(vec, vec![1, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 9]);
+//! ```
 
-pub fn shell_sort<Item>(vec: &mut Vec<Item>)
-    where Item: PartialOrd {
-    let size = vec.length();
-    let hs = generate_hs(size);
-    for h in hs.iter().rev() {
-        h_sort(vec, *h);
+use std::cmp::Ordering;
+use std::ops::{Index, IndexMut};
+use ::index_ops::{Length, Swap};
+
+/// a sequence of increments for shell sort
+pub trait GapSequence {
+    /// the increments that are not greater than size, in ascending order, starting with 1
+    fn gaps(&self, size: usize) -> Vec<usize>;
+}
+
+/// Knuth's increments 1, 4, 13, 40, ..., (3<sup>k</sup> - 1) / 2, as presented in the course.
+/// Shell sort takes time proportional to N<sup>3/2</sup> with them in the worst case.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Knuth;
+
+/// Sedgewick's increments from 1986: 1, 8, 23, 77, 281, ..., 4<sup>k</sup> + 3·2<sup>k-1</sup> + 1.
+/// Shell sort takes time proportional to N<sup>4/3</sup> with them in the worst case.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sedgewick;
+
+/// Tokuda's increments 1, 4, 9, 20, 46, 103, ..., ⌈(9<sup>k</sup> - 4<sup>k</sup>) / (5·4<sup>k-1</sup>)⌉,
+/// which grow by a factor of about 2.25.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tokuda;
+
+/// Ciura's empirically found increments 1, 4, 10, 23, 57, 132, 301, 701, 1750, extended by a
+/// factor of 2.25. They make for the fewest compares on average of the known sequences.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ciura;
+
+/// Pratt's increments 1, 2, 3, 4, 6, 8, 9, 12, ..., all products of powers of 2 and 3. Shell sort
+/// takes time proportional to N lg<sup>2</sup> N with them, also in the worst case, but the
+/// many increments make it slow in practice.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Pratt;
+
+impl GapSequence for Knuth {
+    fn gaps(&self, size: usize) -> Vec<usize> {
+        gaps_from(1, size, |h| h * 3 + 1)
     }
 }
 
-fn generate_hs(size: usize) -> Vec<usize> {
-    let mut vec = vec![1usize];
+impl GapSequence for Sedgewick {
+    fn gaps(&self, size: usize) -> Vec<usize> {
+        let mut gaps = vec![1];
+        let mut k = 1;
+        loop {
+            let h = (1 << (2 * k)) + 3 * (1 << (k - 1)) + 1;
+            if h > size {
+                return gaps;
+            }
+            gaps.push(h);
+            k += 1;
+        }
+    }
+}
+
+impl GapSequence for Tokuda {
+    fn gaps(&self, size: usize) -> Vec<usize> {
+        let mut gaps = vec![1];
+        // the exact increment, before rounding up: h_k+1 = 2.25 h_k + 1
+        let mut h = 1.0f64;
+        loop {
+            h = 2.25 * h + 1.0;
+            let gap = h.ceil() as usize;
+            if gap > size {
+                return gaps;
+            }
+            gaps.push(gap);
+        }
+    }
+}
+
+/// the increments that Ciura found by experiment
+const CIURA_GAPS: [usize; 9] = [1, 4, 10, 23, 57, 132, 301, 701, 1750];
+
+impl GapSequence for Ciura {
+    fn gaps(&self, size: usize) -> Vec<usize> {
+        let mut gaps: Vec<usize> = CIURA_GAPS.iter().cloned().filter(|h| *h == 1 || *h <= size).collect();
+        if gaps.len() == CIURA_GAPS.len() {
+            let last = gaps.pop().unwrap();
+            gaps.extend(gaps_from(last, size, |h| h * 9 / 4));
+        }
+        gaps
+    }
+}
+
+impl GapSequence for Pratt {
+    fn gaps(&self, size: usize) -> Vec<usize> {
+        let mut gaps = vec![];
+        let mut power_of_2 = 1;
+        loop {
+            gaps.extend(gaps_from(power_of_2, size, |h| h * 3));
+            power_of_2 *= 2;
+            if power_of_2 > size {
+                break;
+            }
+        }
+        gaps.sort();
+        gaps
+    }
+}
+
+/// the increments from first on, that the next function generates, and are not greater than size.
+/// first is included anyway.
+fn gaps_from<F>(first: usize, size: usize, next: F) -> Vec<usize>
+    where F: Fn(usize) -> usize {
+    let mut gaps = vec![first];
     loop {
-        let h_size = vec.len();
-        let new_h = vec[h_size - 1] * 3 + 1;
-        if new_h > size {
-            break;
+        let h = next(gaps[gaps.len() - 1]);
+        if h > size {
+            return gaps;
         }
-        vec.push(new_h);
+        gaps.push(h);
+    }
+}
+
+/// Sorts types that implement [`Index`], [`IndexMut`], [`Length`] and [`Swap`] with the shell sort
+/// algorithm, with Knuth's increments 1, 4, 13, 40, ...
+///
+/// Shell sort is not stable: h-sorting exchanges elements over a distance of h, past elements with
+/// equal keys.
+///
+/// [`Index`]: https://doc.rust-lang.org/std/ops/trait.Index.html
+/// [`IndexMut`]: https://doc.rust-lang.org/std/ops/trait.IndexMut.html
+/// [`Length`]: ../index_ops/trait.Length.html
+/// [`Swap`]: ../index_ops/trait.Swap.html
+pub fn shell_sort<Coll, Item>(vec: &mut Coll)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd {
+    shell_sort_by_less(vec, &Knuth, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// Sorts like [`shell_sort`], in the order defined by the compare function.
+///
+/// [`shell_sort`]: fn.shell_sort.html
+pub fn shell_sort_by<Coll, Item, F>(vec: &mut Coll, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> Ordering {
+    shell_sort_by_less(vec, &Knuth, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts like [`shell_sort`], in the order of the keys that the key function extracts.
+///
+/// [`shell_sort`]: fn.shell_sort.html
+pub fn shell_sort_by_key<Coll, Item, K, F>(vec: &mut Coll, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    shell_sort_by_less(vec, &Knuth, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+/// Sorts like [`shell_sort`], with the increments of the gap sequence.
+///
+/// [`shell_sort`]: fn.shell_sort.html
+pub fn shell_sort_with<Coll, Item, G>(vec: &mut Coll, gaps: &G)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          Item: PartialOrd,
+          G: ?Sized + GapSequence {
+    shell_sort_by_less(vec, gaps, &mut |a: &Item, b: &Item| a < b);
+}
+
+/// Sorts like [`shell_sort_with`], in the order defined by the compare function.
+///
+/// [`shell_sort_with`]: fn.shell_sort_with.html
+pub fn shell_sort_with_by<Coll, Item, G, F>(vec: &mut Coll, gaps: &G, mut compare: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          G: ?Sized + GapSequence,
+          F: FnMut(&Item, &Item) -> Ordering {
+    shell_sort_by_less(vec, gaps, &mut |a: &Item, b: &Item| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts like [`shell_sort_with`], in the order of the keys that the key function extracts.
+///
+/// [`shell_sort_with`]: fn.shell_sort_with.html
+pub fn shell_sort_with_by_key<Coll, Item, G, K, F>(vec: &mut Coll, gaps: &G, mut key: F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          G: ?Sized + GapSequence,
+          K: Ord,
+          F: FnMut(&Item) -> K {
+    shell_sort_by_less(vec, gaps, &mut |a: &Item, b: &Item| key(a) < key(b));
+}
+
+fn shell_sort_by_less<Coll, Item, G, F>(vec: &mut Coll, gaps: &G, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          G: ?Sized + GapSequence,
+          F: FnMut(&Item, &Item) -> bool {
+    let size = vec.length();
+    for h in gaps.gaps(size).iter().rev() {
+        h_sort(vec, *h, less);
     }
-    vec
 }
 
-fn h_sort<Item>(vec: &mut Vec<Item>, h: usize)
-    where Item: PartialOrd {
+/// h-sorts the collection with interleaved h-insertion: it inserts each element into the
+/// subsequence of every hth element before it, so that all h subsequences are sorted in one pass.
+fn h_sort<Coll, Item, F>(vec: &mut Coll, h: usize, less: &mut F)
+    where Coll: ?Sized + Index<usize, Output=Item> + IndexMut<usize, Output=Item> + Length + Swap<Item>,
+          F: FnMut(&Item, &Item) -> bool {
     let size = vec.length();
-    let mut offset = 0;
-    while offset + h < size {
-        insertion_sort(&mut Step::new(vec, h, offset));
-        offset = offset + 1;
+    for i in h..size {
+        let mut j = i;
+        while j >= h && less(&vec[j], &vec[j - h]) {
+            vec.swap(j, j - h);
+            j -= h;
+        }
     }
 }
 
@@ -40,8 +233,11 @@ mod test {
 
     use ::step::Step;
     use ::insertion_sort::insertion_sort;
-    use ::is_sorted::{is_sorted, sort_some};
-    use super::{shell_sort, h_sort, generate_hs};
+    use ::is_sorted::{is_sorted, sort_some, sort_some_by, sort_some_slices};
+    use ::stability::{indexed, is_stably_sorted, sorts_stably};
+    use super::{shell_sort, shell_sort_by, shell_sort_by_key, h_sort};
+    use super::{shell_sort_with, shell_sort_with_by, shell_sort_with_by_key};
+    use super::{GapSequence, Knuth, Sedgewick, Tokuda, Ciura, Pratt};
 
     use self::rand::thread_rng;
 
@@ -142,17 +338,38 @@ mod test {
     #[test]
     fn example_from_course() {
         let mut vec = vec!['S', 'O', 'R', 'T', 'E', 'X', 'A', 'M', 'P', 'L', 'E'];
-        h_sort(&mut vec, 7);
+        h_sort(&mut vec, 7, &mut |a: &char, b: &char| a < b);
         assert_eq!(vec!['M', 'O', 'L', 'E', 'E', 'X', 'A', 'S', 'P', 'R', 'T'], vec);
-        h_sort(&mut vec, 3);
+        h_sort(&mut vec, 3, &mut |a: &char, b: &char| a < b);
         assert_eq!(vec!['A', 'E', 'L', 'E', 'O', 'P', 'M', 'S', 'X', 'R', 'T'], vec);
-        h_sort(&mut vec, 1);
+        h_sort(&mut vec, 1, &mut |a: &char, b: &char| a < b);
         assert_eq!(vec!['A', 'E', 'E', 'L', 'M', 'O', 'P', 'R', 'S', 'T', 'X'], vec);
     }
 
     #[test]
     fn h_generation() {
-        assert_eq!(generate_hs(364), vec![1, 4, 13, 40, 121, 364])
+        assert_eq!(Knuth.gaps(364), vec![1, 4, 13, 40, 121, 364]);
+        assert_eq!(Sedgewick.gaps(1072), vec![1, 8, 23, 77, 281]);
+        assert_eq!(Tokuda.gaps(1200), vec![1, 4, 9, 20, 46, 103, 233, 525, 1182]);
+        assert_eq!(Ciura.gaps(1750), vec![1, 4, 10, 23, 57, 132, 301, 701, 1750]);
+        assert_eq!(Ciura.gaps(9000), vec![1, 4, 10, 23, 57, 132, 301, 701, 1750, 3937, 8858]);
+        assert_eq!(Pratt.gaps(20), vec![1, 2, 3, 4, 6, 8, 9, 12, 16, 18]);
+    }
+
+    #[test]
+    fn gaps_of_small_sizes() {
+        let sequences: Vec<&dyn GapSequence> = vec![&Knuth, &Sedgewick, &Tokuda, &Ciura, &Pratt];
+        for gaps in sequences {
+            assert_eq!(gaps.gaps(0), vec![1]);
+            assert_eq!(gaps.gaps(1), vec![1]);
+        }
+    }
+
+    #[test]
+    fn h_sort_sorts_every_subsequence() {
+        let mut vec = vec![6, 8, 10, 1, 0, 3, 7, 3, 6, 1];
+        h_sort(&mut vec, 4, &mut |a: &i32, b: &i32| a < b);
+        assert_eq!(vec![0, 1, 7, 1, 6, 3, 10, 3, 6, 8], vec);
     }
 
     #[test]
@@ -160,4 +377,44 @@ mod test {
         let mut rng = thread_rng();
         sort_some::<u32>(&mut rng, shell_sort);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn shell_sort_slices() {
+        let mut rng = thread_rng();
+        sort_some_slices::<u32>(&mut rng, shell_sort);
+
+        let mut array = [8, 1, 5, 3, 4, 7, 3, 6, 9, 7, 6, 5, 4, 2];
+        shell_sort(&mut array);
+        assert!(is_sorted(&array));
+    }
+
+    #[test]
+    fn shell_sort_by_some() {
+        let mut rng = thread_rng();
+        sort_some_by(&mut rng,
+                     |slice| shell_sort_by(slice, |a, b| b.cmp(a)),
+                     |slice| shell_sort_by_key(slice, |pair| pair.1));
+    }
+
+    #[test]
+    fn shell_sort_with_some() {
+        let mut rng = thread_rng();
+        sort_some::<u32>(&mut rng, |vec| shell_sort_with(vec, &Sedgewick));
+        sort_some::<u32>(&mut rng, |vec| shell_sort_with(vec, &Tokuda));
+        sort_some::<u32>(&mut rng, |vec| shell_sort_with(vec, &Ciura));
+        sort_some::<u32>(&mut rng, |vec| shell_sort_with(vec, &Pratt));
+        sort_some_slices::<u32>(&mut rng, |slice| shell_sort_with(slice, &Ciura));
+        sort_some_by(&mut rng,
+                     |slice| shell_sort_with_by(slice, &Tokuda, |a, b| b.cmp(a)),
+                     |slice| shell_sort_with_by_key(slice, &Pratt, |pair| pair.1));
+    }
+
+    #[test]
+    fn shell_sort_is_not_stable() {
+        // the 4-sort exchanges the 0 with the first 1, which moves that 1 behind the others
+        let mut vec = indexed(vec![1, 1, 1, 1, 0]);
+        shell_sort(&mut vec);
+        assert!(!is_stably_sorted(&vec));
+        assert!(!sorts_stably(&mut thread_rng(), shell_sort));
+    }
+}
@@ -0,0 +1,117 @@
+//! A harness that checks, whether a sort is stable: whether it keeps elements with equal keys in
+//! the order that they had before sorting.
+//!
+//! The elements are [`Indexed`] keys, that remember their original index, but are compared by
+//! their key only.
+//!
+//! [`Indexed`]: struct.Indexed.html
+//!
+//! # Example
+//! ```
+//! extern crate algorithms;
+//! extern crate rand;
+//!
+//! use algorithms::merge_sort::merge_sort;
+//! use algorithms::selection_sort::selection_sort;
+//! use algorithms::stability::sorts_stably;
+//!
+//! # fn main() {
+//! let mut rng = rand::thread_rng();
+//! assert!(sorts_stably(&mut rng, merge_sort));
+//! assert!(!sorts_stably(&mut rng, selection_sort));
+//! # }
+//! ```
+
+extern crate rand;
+
+use self::rand::{ThreadRng, Rng};
+use std::cmp::Ordering;
+use std::ops::Index;
+use ::index_ops::Length;
+
+/// the number of distinct keys that [`sorts_stably`] uses, so that there are many equal keys
+///
+/// [`sorts_stably`]: fn.sorts_stably.html
+const KEYS: u32 = 10;
+
+/// a key, together with its index before sorting. It is compared by the key only.
+#[derive(Clone, Copy, Debug)]
+pub struct Indexed<K> {
+    pub key: K,
+    pub index: usize,
+}
+
+impl<K> PartialEq for Indexed<K>
+    where K: PartialEq {
+    fn eq(&self, other: &Indexed<K>) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K> PartialOrd for Indexed<K>
+    where K: PartialOrd {
+    fn partial_cmp(&self, other: &Indexed<K>) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+/// the keys, each indexed with its position
+pub fn indexed<K>(keys: Vec<K>) -> Vec<Indexed<K>> {
+    keys.into_iter().enumerate().map(|(index, key)| Indexed { key, index }).collect()
+}
+
+/// whether the collection is sorted by key, and the elements with equal keys are in the order of
+/// their indices
+pub fn is_stably_sorted<Coll, K>(vec: &Coll) -> bool
+    where Coll: ?Sized + Index<usize, Output=Indexed<K>> + Length,
+          K: PartialOrd {
+    let len = vec.length();
+    (1..len).all(|i| match vec[i - 1].key.partial_cmp(&vec[i].key) {
+        Some(Ordering::Less) => true,
+        Some(Ordering::Equal) => vec[i - 1].index < vec[i].index,
+        _ => false,
+    })
+}
+
+/// Sorts some vectors of random keys with many duplicates, and returns whether all of them were
+/// sorted stably.
+pub fn sorts_stably<F>(rng: &mut ThreadRng, mut sort: F) -> bool
+    where F: FnMut(&mut Vec<Indexed<u32>>) {
+    (0..20).map(|x| x * x).all(|size| {
+        let mut vec = indexed((0..size).map(|_| rng.gen_range(0, KEYS)).collect());
+        sort(&mut vec);
+        is_stably_sorted(&vec)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::thread_rng;
+    use super::{indexed, is_stably_sorted, sorts_stably, Indexed};
+
+    #[test]
+    fn compared_by_key() {
+        assert!(Indexed { key: 1, index: 5 } < Indexed { key: 2, index: 0 });
+        assert!(Indexed { key: 1, index: 5 } == Indexed { key: 1, index: 0 });
+    }
+
+    #[test]
+    fn stably_sorted() {
+        assert!(is_stably_sorted(&indexed(vec![1, 1, 2, 3, 3])));
+
+        let mut vec = indexed(vec![1, 1, 2]);
+        vec.swap(0, 1);
+        assert!(!is_stably_sorted(&vec));
+
+        assert!(!is_stably_sorted(&indexed(vec![2, 1])));
+    }
+
+    #[test]
+    fn sorts_stably_detects_instability() {
+        let mut rng = thread_rng();
+        assert!(sorts_stably(&mut rng, |vec| vec.sort_by_key(|indexed| indexed.key)));
+        assert!(!sorts_stably(&mut rng, |vec| vec.reverse()));
+    }
+}
@@ -19,14 +19,14 @@ use std::ops::{Index, IndexMut};
 use ::index_ops::{Length, Swap};
 
 pub struct Step<'a, Underl>
-    where Underl: 'a {
+    where Underl: 'a + ?Sized {
     underl: &'a mut Underl,
     step: usize,
     offset: usize,
 }
 
 impl<'a, Underl, Output> Index<usize> for Step<'a, Underl>
-    where Underl: Index<usize, Output=Output> {
+    where Underl: ?Sized + Index<usize, Output=Output> {
     type Output = Output;
 
     fn index(&self, index: usize) -> &Output {
@@ -35,25 +35,28 @@ impl<'a, Underl, Output> Index<usize> for Step<'a, Underl>
 }
 
 impl<'a, Underl, Output> IndexMut<usize> for Step<'a, Underl>
-    where Underl: 'a + IndexMut<usize, Output=Output> {
+    where Underl: 'a + ?Sized + IndexMut<usize, Output=Output> {
     fn index_mut(&mut self, index: usize) -> &mut Output {
         &mut self.underl[self.offset + index * self.step]
     }
 }
 
-impl<'a, Underl> Step<'a, Underl> {
+impl<'a, Underl> Step<'a, Underl>
+    where Underl: ?Sized {
     pub fn new(underl: &'a mut Underl, step: usize, offset: usize) -> Step<'a, Underl> {
         Step { underl, step, offset }
     }
 }
 
-impl<'a, Item> Length for Step<'a, Vec<Item>> {
+impl<'a, Underl> Length for Step<'a, Underl>
+    where Underl: ?Sized + Length {
     fn length(&self) -> usize {
-        (self.underl.len() - self.offset + self.step - 1) / self.step
+        (self.underl.length() - self.offset + self.step - 1) / self.step
     }
 }
 
-impl<'a, Item> Swap<Item> for Step<'a, Vec<Item>> {
+impl<'a, Underl, Item> Swap<Item> for Step<'a, Underl>
+    where Underl: ?Sized + Swap<Item> {
     fn swap(&mut self, a: usize, b: usize) {
         self.underl.swap(self.offset + a * self.step, self.offset + b * self.step)
     }
@@ -0,0 +1,95 @@
+//! an adapter for types that implement [`Index`] and [`IndexMut`],
+//! to index into a range of them, as if it was a collection of its own.
+//!
+//! [`Index`]: https://doc.rust-lang.org/std/ops/trait.Index.html
+//! [`IndexMut`]: https://doc.rust-lang.org/std/ops/trait.IndexMut.html
+//!
+//! # Example
+//! ```
+//! use algorithms::index_ops::Length;
+//! use algorithms::sub_range::SubRange;
+//!
+//! let mut vec = vec![1, 2, 3, 4, 5];
+//! let sub_range = SubRange::new(&mut vec, 1, 3);
+//!
+//! assert_eq!(sub_range[0], 2);
+//! assert_eq!(sub_range[1], 3);
+//! assert_eq!(sub_range.length(), 2);
+//! ```
+
+use std::ops::{Index, IndexMut};
+use ::index_ops::{Length, Swap};
+
+pub struct SubRange<'a, Underl>
+    where Underl: 'a + ?Sized {
+    underl: &'a mut Underl,
+    lo: usize,
+    hi: usize,
+}
+
+impl<'a, Underl, Output> Index<usize> for SubRange<'a, Underl>
+    where Underl: ?Sized + Index<usize, Output=Output> {
+    type Output = Output;
+
+    fn index(&self, index: usize) -> &Output {
+        &self.underl[self.lo + index]
+    }
+}
+
+impl<'a, Underl, Output> IndexMut<usize> for SubRange<'a, Underl>
+    where Underl: 'a + ?Sized + IndexMut<usize, Output=Output> {
+    fn index_mut(&mut self, index: usize) -> &mut Output {
+        &mut self.underl[self.lo + index]
+    }
+}
+
+impl<'a, Underl> SubRange<'a, Underl>
+    where Underl: ?Sized {
+    /// the range from lo to (excluding) hi of underl
+    pub fn new(underl: &'a mut Underl, lo: usize, hi: usize) -> SubRange<'a, Underl> {
+        SubRange { underl, lo, hi }
+    }
+}
+
+impl<'a, Underl> Length for SubRange<'a, Underl>
+    where Underl: ?Sized {
+    fn length(&self) -> usize {
+        self.hi - self.lo
+    }
+}
+
+impl<'a, Underl, Item> Swap<Item> for SubRange<'a, Underl>
+    where Underl: ?Sized + Swap<Item> {
+    fn swap(&mut self, a: usize, b: usize) {
+        self.underl.swap(self.lo + a, self.lo + b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SubRange;
+    use ::index_ops::{Length, Swap};
+
+    #[test]
+    fn index() {
+        let mut vec = vec![1, 2, 3, 4, 5];
+        let sub_range = SubRange::new(&mut vec, 2, 5);
+
+        assert_eq!(sub_range[0], 3);
+        assert_eq!(sub_range[2], 5);
+        assert_eq!(sub_range.length(), 3);
+    }
+
+    #[test]
+    fn index_mut_and_swap() {
+        let mut vec = vec![1, 2, 3, 4, 5];
+        {
+            let mut sub_range = SubRange::new(&mut vec, 1, 4);
+
+            sub_range[0] = 10;
+            sub_range.swap(1, 2);
+        }
+
+        assert_eq!(vec, vec![1, 10, 4, 3, 5]);
+    }
+}
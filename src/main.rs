@@ -1,6 +1,5 @@
 extern crate algorithms;
 extern crate rand;
-extern crate stopwatch;
 
 use algorithms::percolation::PercolationState;
 use algorithms::performance;
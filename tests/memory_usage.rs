@@ -0,0 +1,146 @@
+extern crate algorithms;
+extern crate rand;
+
+use algorithms::deque::{Deque, DequeT};
+use algorithms::graph::{Graph, GraphImplType};
+use algorithms::percolation::{Percolation, PercolationState};
+use algorithms::performance::{PerformanceObservable, TrackingAllocator, observe_memory};
+use algorithms::red_black_bst_2::Tree;
+use rand::{ThreadRng, Rng, RngCore};
+
+// installed in this test crate only, to not slow down the timing in the performance tests
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+struct TreeWrap {
+    tree: Tree<i32>,
+}
+
+impl PerformanceObservable for TreeWrap {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> TreeWrap {
+        let mut tree = Tree::<i32>::new();
+        for _ in 0..size {
+            tree.insert(rng.gen());
+        }
+
+        TreeWrap {
+            tree
+        }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        self.tree.insert(rng.gen());
+    }
+}
+
+struct DequeWrap {
+    deque: Deque<u32>
+}
+
+impl PerformanceObservable for DequeWrap {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> DequeWrap {
+        let mut deque = Deque::<u32>::new();
+
+        while deque.size() < size {
+            deque.add_first(rng.next_u32());
+            deque.add_last(rng.next_u32());
+        }
+
+        DequeWrap {
+            deque
+        }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        self.deque.add_first(rng.next_u32());
+    }
+}
+
+struct GraphWrap {
+    graph: GraphImplType,
+}
+
+impl PerformanceObservable for GraphWrap {
+    // a graph with as many random edges as vertices
+    fn prepare(size: usize, rng: &mut ThreadRng) -> GraphWrap {
+        let mut graph: GraphImplType = Graph::new(size);
+        for _ in 0..size {
+            graph.add_edge(rng.gen_range(0, size), rng.gen_range(0, size));
+        }
+
+        GraphWrap {
+            graph
+        }
+    }
+
+    fn run(&mut self, size: usize, rng: &mut ThreadRng) {
+        self.graph.add_edge(rng.gen_range(0, size), rng.gen_range(0, size));
+    }
+}
+
+struct PercolationWrap {
+    perc: PercolationState,
+}
+
+impl PerformanceObservable for PercolationWrap {
+    // a size-by-size grid
+    fn prepare(size: usize, _rng: &mut ThreadRng) -> PercolationWrap {
+        PercolationWrap {
+            perc: Percolation::new(size)
+        }
+    }
+
+    fn run(&mut self, size: usize, rng: &mut ThreadRng) {
+        for _ in 0..size {
+            self.perc.open(rng.gen_range(0, size), rng.gen_range(0, size));
+        }
+    }
+}
+
+#[test]
+fn test_tree() {
+    let report = observe_memory::<TreeWrap>(1 << 16);
+    println!("{}", report);
+    let b = report.power_law().unwrap().b;
+    println!("Exponent tree memory = {}", b);
+
+    assert!(b > 0.95 && b < 1.05);
+}
+
+#[test]
+fn test_deque() {
+    let report = observe_memory::<DequeWrap>(1 << 16);
+    println!("{}", report);
+    let b = report.power_law().unwrap().b;
+    println!("Exponent deque memory = {}", b);
+
+    assert!(b > 0.95 && b < 1.05);
+    // a node holds the item and two pointers
+    for sample in report.samples() {
+        assert_eq!(sample.run.live_bytes, 24);
+    }
+}
+
+#[test]
+fn test_graph() {
+    let report = observe_memory::<GraphWrap>(1 << 16);
+    println!("{}", report);
+    let b = report.power_law().unwrap().b;
+    println!("Exponent graph memory = {}", b);
+
+    assert!(b > 0.9 && b < 1.1);
+}
+
+#[test]
+fn test_percolation() {
+    let report = observe_memory::<PercolationWrap>(1 << 10);
+    println!("{}", report);
+    let b = report.power_law().unwrap().b;
+    println!("Exponent percolation memory = {}", b);
+
+    // the grid has N^2 sites
+    assert!(b > 1.9 && b < 2.1);
+    for sample in report.samples() {
+        assert_eq!(sample.run.live_bytes, 0);
+    }
+}
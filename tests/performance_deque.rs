@@ -2,7 +2,7 @@ extern crate algorithms;
 extern crate rand;
 
 use algorithms::deque::{Deque, DequeT};
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, RngCore};
 
 struct DequeWrap {
@@ -30,6 +30,6 @@ impl PerformanceObservable for DequeWrap {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<DequeWrap>(1000i64, Option::Some(2000000usize));
-    assert!(factor < 1.2f64);
+    let report = observe::<DequeWrap>(1000i64, Option::Some(2000000usize));
+    check_exponent("deque", &report, ..0.3);
 }
\ No newline at end of file
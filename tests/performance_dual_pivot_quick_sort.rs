@@ -0,0 +1,47 @@
+extern crate algorithms;
+extern crate rand;
+
+use algorithms::quick_sort::dual_pivot_quick_sort;
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
+use rand::{ThreadRng, Rng};
+
+struct VecWrap {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for VecWrap {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> VecWrap {
+        VecWrap { vec: (0..size).map(|_| rng.gen()).collect() }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        dual_pivot_quick_sort(&mut self.vec, rng);
+    }
+}
+
+struct DuplicateKeys {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for DuplicateKeys {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> DuplicateKeys {
+        // 3 distinct keys
+        DuplicateKeys { vec: (0..size).map(|_| rng.gen_range(0, 3)).collect() }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        dual_pivot_quick_sort(&mut self.vec, rng);
+    }
+}
+
+#[test]
+fn test_performance() {
+    let report = observe::<VecWrap>(5000i64, None);
+    check_exponent("dual_pivot_quick_sort", &report, 0.9..1.5);
+}
+
+#[test]
+fn test_performance_duplicate_keys() {
+    let report = observe::<DuplicateKeys>(5000i64, None);
+    check_exponent("dual_pivot_quick_sort_duplicate_keys", &report, 0.7..1.5);
+}
\ No newline at end of file
@@ -1,8 +1,8 @@
 extern crate algorithms;
 extern crate rand;
 
-use algorithms::max_pq::{make_heap, heap_sort};
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::max_pq::heap_sort;
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, Rng};
 
 struct VecWrap {
@@ -19,16 +19,12 @@ impl PerformanceObservable for VecWrap {
     }
 
     fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
-        make_heap(&mut self.vec);
         heap_sort(&mut self.vec);
     }
 }
 
 #[test]
 fn test_performance() {
-    let factor = observe::<VecWrap>(5000i64, None);
-    println!("Factor heap_sort = {}", factor);
-
-    assert!(factor > 2.0);
-    assert!(factor < 3.5);
+    let report = observe::<VecWrap>(5000i64, None);
+    check_exponent("heap_sort", &report, 0.9..1.5);
 }
@@ -2,7 +2,7 @@ extern crate algorithms;
 extern crate rand;
 
 use algorithms::insertion_sort::insertion_sort;
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, Rng};
 
 
@@ -26,11 +26,8 @@ impl PerformanceObservable for VecWrap {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<VecWrap>(20000i64, None);
-    println!("Factor insertion sort = {}", factor);
-
-    assert!(factor > 3.5);
-    assert!(factor < 4.8);
+    let report = observe::<VecWrap>(20000i64, None);
+    check_exponent("insertion_sort", &report, 1.7..2.3);
 }
 
 struct PartiallySorted {
@@ -61,9 +58,6 @@ impl PerformanceObservable for PartiallySorted {
 
 #[test]
 fn test_performance_partially_sorted() {
-    let factor = observe::<PartiallySorted>(5000i64, None);
-    println!("Factor insertion sort = {}", factor);
-
-    assert!(factor > 1.8);
-    assert!(factor < 2.4);
+    let report = observe::<PartiallySorted>(5000i64, None);
+    check_exponent("insertion_sort_partially_sorted", &report, 0.8..1.3);
 }
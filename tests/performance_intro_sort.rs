@@ -0,0 +1,66 @@
+extern crate algorithms;
+extern crate rand;
+
+use algorithms::quick_sort::intro_sort;
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
+use rand::{ThreadRng, Rng};
+
+struct VecWrap {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for VecWrap {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> VecWrap {
+        VecWrap { vec: (0..size).map(|_| rng.gen()).collect() }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        intro_sort(&mut self.vec);
+    }
+}
+
+// the keys that make quick sort quadratic, when it does not stop scanning at equal keys
+struct DuplicateKeys {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for DuplicateKeys {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> DuplicateKeys {
+        DuplicateKeys { vec: (0..size).map(|_| rng.gen_range(0, 3)).collect() }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        intro_sort(&mut self.vec);
+    }
+}
+
+// sorted input, that makes quick sort quadratic without a shuffle, when it partitions on the
+// first element
+struct SortedVecWrap {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for SortedVecWrap {
+    fn prepare(size: usize, _rng: &mut ThreadRng) -> SortedVecWrap {
+        SortedVecWrap { vec: (0..size as u32).collect() }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        intro_sort(&mut self.vec);
+    }
+}
+
+#[test]
+fn test_performance() {
+    check_exponent("intro_sort", &observe::<VecWrap>(5000i64, None), 0.9..1.5);
+}
+
+#[test]
+fn test_performance_duplicate_keys() {
+    check_exponent("intro_sort_duplicate_keys", &observe::<DuplicateKeys>(5000i64, None), 0.9..1.5);
+}
+
+#[test]
+fn test_performance_sorted() {
+    check_exponent("intro_sort_sorted", &observe::<SortedVecWrap>(5000i64, None), 0.9..1.5);
+}
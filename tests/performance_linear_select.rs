@@ -0,0 +1,28 @@
+extern crate algorithms;
+extern crate rand;
+
+use algorithms::select::linear_select;
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
+use rand::{ThreadRng, Rng};
+
+struct Input {
+    vec: Vec<u32>,
+    k: usize
+}
+
+impl PerformanceObservable for Input {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> Input {
+        let vec: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+        let k = rng.gen_range(0, size);
+
+        Input { vec, k }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) { linear_select(&mut self.vec, self.k); }
+}
+
+#[test]
+fn test_performance() {
+    let report = observe::<Input>(5000i64, None);
+    check_exponent("linear_select", &report, 0.7..1.3);
+}
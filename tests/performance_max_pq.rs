@@ -2,7 +2,7 @@ extern crate algorithms;
 extern crate rand;
 
 use algorithms::max_pq::MaxPQ;
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, Rng};
 
 struct VecWrap {
@@ -28,9 +28,6 @@ impl PerformanceObservable for VecWrap {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<VecWrap>(30i64, None);
-    println!("Factor binary heap insert, delete = {}", factor);
-
-    assert!(factor > 1.0);
-    assert!(factor < 2.0);
+    let report = observe::<VecWrap>(30i64, None);
+    check_exponent("max_pq", &report, ..0.5);
 }
\ No newline at end of file
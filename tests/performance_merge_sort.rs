@@ -2,7 +2,7 @@ extern crate algorithms;
 extern crate rand;
 
 use algorithms::merge_sort::merge_sort;
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, Rng};
 
 struct VecWrap {
@@ -25,9 +25,6 @@ impl PerformanceObservable for VecWrap {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<VecWrap>(5000i64, None);
-    println!("Factor selection sort = {}", factor);
-
-    assert!(factor > 2.0);
-    assert!(factor < 3.5);
+    let report = observe::<VecWrap>(5000i64, None);
+    check_exponent("merge_sort", &report, 0.9..1.5);
 }
\ No newline at end of file
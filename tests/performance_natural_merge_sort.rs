@@ -0,0 +1,47 @@
+extern crate algorithms;
+extern crate rand;
+
+use algorithms::merge_sort::natural_merge_sort;
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
+use rand::{ThreadRng, Rng};
+
+struct VecWrap {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for VecWrap {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> VecWrap {
+        VecWrap { vec: (0..size).map(|_| rng.gen()).collect() }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        natural_merge_sort(&mut self.vec);
+    }
+}
+
+// already sorted input, on which natural merge sort is linear
+struct SortedVecWrap {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for SortedVecWrap {
+    fn prepare(size: usize, _rng: &mut ThreadRng) -> SortedVecWrap {
+        SortedVecWrap { vec: (0..size as u32).collect() }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        natural_merge_sort(&mut self.vec);
+    }
+}
+
+#[test]
+fn test_performance() {
+    let report = observe::<VecWrap>(5000i64, None);
+    check_exponent("natural_merge_sort", &report, 0.9..1.5);
+}
+
+#[test]
+fn test_performance_sorted() {
+    let report = observe::<SortedVecWrap>(5000i64, None);
+    check_exponent("natural_merge_sort_sorted", &report, 0.8..1.25);
+}
@@ -0,0 +1,100 @@
+extern crate algorithms;
+extern crate rand;
+
+use std::thread;
+use algorithms::merge_sort::merge_sort;
+use algorithms::parallel_sort::{parallel_merge_sort, parallel_quick_sort};
+use algorithms::performance::{PerformanceObservable, ObservationReport, check_exponent, observe, relative_speed};
+use algorithms::quick_sort::intro_sort;
+use rand::{ThreadRng, Rng};
+
+const MAX_SIZE: usize = 1 << 22;
+
+fn random(size: usize, rng: &mut ThreadRng) -> Vec<u32> {
+    (0..size).map(|_| rng.gen()).collect()
+}
+
+struct MergeSort {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for MergeSort {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> MergeSort {
+        MergeSort { vec: random(size, rng) }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        merge_sort(&mut self.vec);
+    }
+}
+
+struct ParallelMergeSort {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for ParallelMergeSort {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> ParallelMergeSort {
+        ParallelMergeSort { vec: random(size, rng) }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        parallel_merge_sort(&mut self.vec);
+    }
+}
+
+struct QuickSort {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for QuickSort {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> QuickSort {
+        QuickSort { vec: random(size, rng) }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        intro_sort(&mut self.vec);
+    }
+}
+
+struct ParallelQuickSort {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for ParallelQuickSort {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> ParallelQuickSort {
+        ParallelQuickSort { vec: random(size, rng) }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        parallel_quick_sort(&mut self.vec);
+    }
+}
+
+// the speedup of the parallel sort over the sequential one, for the largest size both sorted
+fn check_speedup(name: &str, sequential: &ObservationReport, parallel: &ObservationReport) {
+    let (size, speedup) = relative_speed(sequential, parallel).unwrap();
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    println!("Speedup {} on {} threads, for size {} = {}", name, threads, size, speedup);
+
+    if threads < 4 || size < 1 << 18 {
+        println!("Too few threads or too small sizes to check the speedup");
+        return;
+    }
+    assert!(speedup > 1.5);
+}
+
+#[test]
+fn test_performance_merge_sort() {
+    let sequential = observe::<MergeSort>(5000i64, Some(MAX_SIZE));
+    let parallel = observe::<ParallelMergeSort>(5000i64, Some(MAX_SIZE));
+    check_exponent("parallel_merge_sort", &parallel, 0.9..1.5);
+    check_speedup("parallel merge sort", &sequential, &parallel);
+}
+
+#[test]
+fn test_performance_quick_sort() {
+    let sequential = observe::<QuickSort>(5000i64, Some(MAX_SIZE));
+    let parallel = observe::<ParallelQuickSort>(5000i64, Some(MAX_SIZE));
+    check_exponent("parallel_quick_sort", &parallel, 0.9..1.5);
+    check_speedup("parallel quick sort", &sequential, &parallel);
+}
@@ -0,0 +1,67 @@
+extern crate algorithms;
+extern crate rand;
+
+use algorithms::d_ary_heap::DAryHeap;
+use algorithms::leftist_heap::LeftistHeap;
+use algorithms::max_pq::MaxPQ;
+use algorithms::pairing_heap::PairingHeap;
+use algorithms::performance::{PerformanceObservable, ObservationReport, check_exponent, observe, relative_speed};
+use rand::{ThreadRng, Rng};
+
+const MAX_MILLIS: i64 = 3000;
+const MAX_SIZE: usize = 1 << 16;
+
+// inserts into and deletes from a priority queue of the given size, like the benchmark of the
+// binary heap in performance_max_pq
+struct InsertDelete<PQ> {
+    pq: PQ
+}
+
+impl<PQ> PerformanceObservable for InsertDelete<PQ>
+    where PQ: MaxPQ<Item=u32> + Default {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> InsertDelete<PQ> {
+        let mut pq = PQ::default();
+        for _i in 0..size {
+            pq.heap_insert(rng.gen());
+        }
+        InsertDelete { pq }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        for _count in 0..5000 {
+            self.pq.heap_insert(rng.gen());
+            self.pq.del_max();
+        }
+    }
+}
+
+// the speed of the other priority queue relative to the binary heap, for the largest size both
+// observed
+fn print_relative_speed(name: &str, other: &ObservationReport, binary: &ObservationReport) {
+    let (size, speed) = relative_speed(binary, other).unwrap();
+    println!("Speed of {} relative to the binary heap, for size {} = {}", name, size, speed);
+}
+
+#[test]
+fn test_performance_d_ary_heap() {
+    let report = observe::<InsertDelete<DAryHeap<u32>>>(MAX_MILLIS, Some(MAX_SIZE));
+    check_exponent("d_ary_heap", &report, ..0.5);
+    let binary = observe::<InsertDelete<Vec<u32>>>(MAX_MILLIS, Some(MAX_SIZE));
+    print_relative_speed("the 4-ary heap", &report, &binary);
+}
+
+#[test]
+fn test_performance_pairing_heap() {
+    let report = observe::<InsertDelete<PairingHeap<u32>>>(MAX_MILLIS, Some(MAX_SIZE));
+    check_exponent("pairing_heap", &report, ..0.5);
+    let binary = observe::<InsertDelete<Vec<u32>>>(MAX_MILLIS, Some(MAX_SIZE));
+    print_relative_speed("the pairing heap", &report, &binary);
+}
+
+#[test]
+fn test_performance_leftist_heap() {
+    let report = observe::<InsertDelete<LeftistHeap<u32>>>(MAX_MILLIS, Some(MAX_SIZE));
+    check_exponent("leftist_heap", &report, ..0.5);
+    let binary = observe::<InsertDelete<Vec<u32>>>(MAX_MILLIS, Some(MAX_SIZE));
+    print_relative_speed("the leftist heap", &report, &binary);
+}
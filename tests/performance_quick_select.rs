@@ -2,7 +2,7 @@ extern crate algorithms;
 extern crate rand;
 
 use algorithms::quick_sort::quick_select;
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, Rng};
 
 struct Input {
@@ -26,10 +26,6 @@ impl PerformanceObservable for Input {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<Input>(5000i64, None);
-
-    println!("Factor selection sort = {}", factor);
-
-    assert!(factor > 1.8);
-    assert!(factor < 2.5);
+    let report = observe::<Input>(5000i64, None);
+    check_exponent("quick_select", &report, 0.7..1.3);
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ extern crate algorithms;
 extern crate rand;
 
 use algorithms::quick_sort::quick_sort;
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, Rng};
 
 struct VecWrap {
@@ -43,18 +43,12 @@ impl PerformanceObservable for DuplicateKeys {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<VecWrap>(5000i64, None);
-    println!("Factor selection sort = {}", factor);
-
-    assert!(factor > 2.0);
-    assert!(factor < 3.5);
+    let report = observe::<VecWrap>(5000i64, None);
+    check_exponent("quick_sort", &report, 0.9..1.5);
 }
 
 #[test]
 fn test_performance_duplicate_keys() {
-    let factor = observe::<DuplicateKeys>(5000i64, None);
-    println!("Factor selection sort = {}", factor);
-
-    assert!(factor > 3.5);
-    assert!(factor < 4.5);
+    let report = observe::<DuplicateKeys>(5000i64, None);
+    check_exponent("quick_sort_duplicate_keys", &report, 1.7..2.3);
 }
\ No newline at end of file
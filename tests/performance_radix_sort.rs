@@ -0,0 +1,122 @@
+extern crate algorithms;
+extern crate rand;
+
+use algorithms::performance::{PerformanceObservable, ObservationReport, check_exponent, observe, relative_speed};
+use algorithms::quick_sort::quick_sort;
+use algorithms::radix_sort::{msd_sort, radix_sort, three_way_string_quick_sort};
+use rand::{ThreadRng, Rng};
+
+const MAX_SIZE: usize = 1 << 20;
+// the ids, and the auxiliary array of radix sort, fit in the cache, so its exponent stays linear
+const MAX_IDS: usize = 1 << 18;
+
+fn random_strings(size: usize, rng: &mut ThreadRng) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            let len = rng.gen_range(1, 16);
+            (0..len).map(|_| rng.gen_range(b'a', b'z' + 1) as char).collect()
+        })
+        .collect()
+}
+
+struct Ids {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for Ids {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> Ids {
+        Ids { vec: (0..size).map(|_| rng.gen()).collect() }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        radix_sort(&mut self.vec);
+    }
+}
+
+struct IdsQuickSort {
+    vec: Vec<u32>
+}
+
+impl PerformanceObservable for IdsQuickSort {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> IdsQuickSort {
+        IdsQuickSort { vec: (0..size).map(|_| rng.gen()).collect() }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        quick_sort(&mut self.vec, rng);
+    }
+}
+
+struct Strings {
+    vec: Vec<String>
+}
+
+impl PerformanceObservable for Strings {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> Strings {
+        Strings { vec: random_strings(size, rng) }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        msd_sort(&mut self.vec);
+    }
+}
+
+struct StringsThreeWay {
+    vec: Vec<String>
+}
+
+impl PerformanceObservable for StringsThreeWay {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> StringsThreeWay {
+        StringsThreeWay { vec: random_strings(size, rng) }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        three_way_string_quick_sort(&mut self.vec, rng);
+    }
+}
+
+struct StringsQuickSort {
+    vec: Vec<String>
+}
+
+impl PerformanceObservable for StringsQuickSort {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> StringsQuickSort {
+        StringsQuickSort { vec: random_strings(size, rng) }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        quick_sort(&mut self.vec, rng);
+    }
+}
+
+// the speedup of the other sort over quick sort, for the largest size both sorted
+fn speedup(name: &str, other: &ObservationReport, quick_sort: &ObservationReport) -> f64 {
+    let (size, speedup) = relative_speed(quick_sort, other).unwrap();
+    println!("Speedup of {} over quick sort, for size {} = {}", name, size, speedup);
+    speedup
+}
+
+#[test]
+fn test_performance_radix_sort() {
+    let report = observe::<Ids>(5000i64, Some(MAX_IDS));
+    // a fixed number of passes over the keys: linear, below N lg N
+    check_exponent("radix_sort", &report, 0.8..1.15);
+    let quick_sort = observe::<IdsQuickSort>(5000i64, Some(MAX_IDS));
+    assert!(speedup("radix sort", &report, &quick_sort) > 2.0);
+}
+
+#[test]
+fn test_performance_msd_sort() {
+    let report = observe::<Strings>(5000i64, Some(MAX_SIZE));
+    check_exponent("msd_sort", &report, 0.8..1.5);
+    let quick_sort = observe::<StringsQuickSort>(5000i64, Some(MAX_SIZE));
+    assert!(speedup("msd sort", &report, &quick_sort) > 1.0);
+}
+
+#[test]
+fn test_performance_three_way_string_quick_sort() {
+    let report = observe::<StringsThreeWay>(5000i64, Some(MAX_SIZE));
+    check_exponent("three_way_string_quick_sort", &report, 0.8..1.5);
+    let quick_sort = observe::<StringsQuickSort>(5000i64, Some(MAX_SIZE));
+    assert!(speedup("3-way string quick sort", &report, &quick_sort) > 1.0);
+}
@@ -1,7 +1,7 @@
 extern crate algorithms;
 extern crate rand;
 
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, ObservationConfig, observe_with};
 use algorithms::red_black_bst_2::Tree;
 use rand::{ThreadRng, Rng};
 
@@ -31,8 +31,12 @@ impl PerformanceObservable for TreeWrap {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<TreeWrap>(1000i64, Some(2000000));
-    println!("Factor tree insert = {}", factor);
-
-    assert!(factor < 2.0);
+    // preparing the big trees takes much longer than running the inserts against them
+    let config = ObservationConfig {
+        warm_up_runs: 0,
+        repetitions: 3,
+        ..ObservationConfig::new(1000i64, Some(1000000))
+    };
+    let report = observe_with::<TreeWrap>(&config);
+    check_exponent("red_black", &report, ..0.5);
 }
\ No newline at end of file
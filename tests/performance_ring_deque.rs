@@ -0,0 +1,72 @@
+extern crate algorithms;
+extern crate rand;
+
+use algorithms::deque::{Deque, DequeT};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe, relative_speed};
+use algorithms::randomized_queue::RandomizedQueue;
+use algorithms::ring_deque::RingDeque;
+use rand::{ThreadRng, RngCore};
+
+const MAX_MILLIS: i64 = 3000;
+const MAX_SIZE: usize = 1 << 20;
+
+// removes from and adds to both ends of a deque of the given size, as a high-throughput queue does.
+// Removing first keeps a full ring deque from doubling in the measured run.
+struct AddRemove<D> {
+    deque: D
+}
+
+impl<D> PerformanceObservable for AddRemove<D>
+    where D: DequeT<Item=u32> + Default {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> AddRemove<D> {
+        let mut deque = D::default();
+        while deque.size() < size {
+            deque.add_first(rng.next_u32());
+            deque.add_last(rng.next_u32());
+        }
+        AddRemove { deque }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        for _count in 0..5000 {
+            self.deque.remove_first();
+            self.deque.add_last(rng.next_u32());
+            self.deque.remove_last();
+            self.deque.add_first(rng.next_u32());
+        }
+    }
+}
+
+struct RandomizedQueueWrap {
+    queue: RandomizedQueue<u32>
+}
+
+impl PerformanceObservable for RandomizedQueueWrap {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> RandomizedQueueWrap {
+        RandomizedQueueWrap { queue: (0..size).map(|_| rng.next_u32()).collect() }
+    }
+
+    fn run(&mut self, _size: usize, rng: &mut ThreadRng) {
+        for _count in 0..5000 {
+            self.queue.enqueue(rng.next_u32());
+            self.queue.dequeue();
+        }
+    }
+}
+
+#[test]
+fn test_performance_ring_deque() {
+    let report = observe::<AddRemove<RingDeque<u32>>>(MAX_MILLIS, Some(MAX_SIZE));
+    check_exponent("ring_deque", &report, ..0.3);
+
+    let linked = observe::<AddRemove<Deque<u32>>>(MAX_MILLIS, Some(MAX_SIZE));
+    check_exponent("deque_add_remove", &linked, ..0.3);
+    let (size, speed) = relative_speed(&linked, &report).unwrap();
+    println!("Speed of the ring deque relative to the linked deque, for size {} = {}", size, speed);
+}
+
+#[test]
+fn test_performance_randomized_queue() {
+    let report = observe::<RandomizedQueueWrap>(MAX_MILLIS, Some(MAX_SIZE));
+    check_exponent("randomized_queue", &report, ..0.3);
+}
@@ -2,7 +2,7 @@ extern crate algorithms;
 extern crate rand;
 
 use algorithms::selection_sort::selection_sort;
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, Rng};
 
 
@@ -26,9 +26,6 @@ impl PerformanceObservable for VecWrap {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<VecWrap>(5000i64, None);
-    println!("Factor selection sort = {}", factor);
-
-    assert!(factor > 3.5);
-    assert!(factor < 4.8);
+    let report = observe::<VecWrap>(5000i64, None);
+    check_exponent("selection_sort", &report, 1.7..2.3);
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ extern crate algorithms;
 extern crate rand;
 
 use algorithms::shell_sort::shell_sort;
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, Rng};
 
 
@@ -26,9 +26,6 @@ impl PerformanceObservable for VecWrap {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<VecWrap>(20000i64, None);
-    println!("Factor shell sort = {}", factor);
-
-    assert!(factor > 3.5);
-    assert!(factor < 4.8);
+    let report = observe::<VecWrap>(20000i64, None);
+    check_exponent("shell_sort", &report, 0.9..1.5);
 }
\ No newline at end of file
@@ -0,0 +1,47 @@
+extern crate algorithms;
+extern crate rand;
+
+use algorithms::shell_sort::{shell_sort_with, GapSequence, Knuth, Sedgewick, Tokuda, Ciura, Pratt};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
+use rand::{ThreadRng, Rng};
+
+struct VecWrap<G> {
+    vec: Vec<u32>,
+    gaps: G,
+}
+
+impl<G> PerformanceObservable for VecWrap<G>
+    where G: GapSequence + Default {
+    fn prepare(size: usize, rng: &mut ThreadRng) -> VecWrap<G> {
+        VecWrap { vec: (0..size).map(|_| rng.gen()).collect(), gaps: G::default() }
+    }
+
+    fn run(&mut self, _size: usize, _rng: &mut ThreadRng) {
+        shell_sort_with(&mut self.vec, &self.gaps);
+    }
+}
+
+#[test]
+fn test_performance_knuth() {
+    check_exponent("shell_sort_knuth", &observe::<VecWrap<Knuth>>(5000i64, None), 0.9..1.5);
+}
+
+#[test]
+fn test_performance_sedgewick() {
+    check_exponent("shell_sort_sedgewick", &observe::<VecWrap<Sedgewick>>(5000i64, None), 0.9..1.5);
+}
+
+#[test]
+fn test_performance_tokuda() {
+    check_exponent("shell_sort_tokuda", &observe::<VecWrap<Tokuda>>(5000i64, None), 0.9..1.5);
+}
+
+#[test]
+fn test_performance_ciura() {
+    check_exponent("shell_sort_ciura", &observe::<VecWrap<Ciura>>(5000i64, None), 0.9..1.5);
+}
+
+#[test]
+fn test_performance_pratt() {
+    check_exponent("shell_sort_pratt", &observe::<VecWrap<Pratt>>(5000i64, None), 0.9..1.5);
+}
@@ -2,7 +2,7 @@ extern crate algorithms;
 extern crate rand;
 
 use algorithms::quick_sort::three_way_quick_sort;
-use algorithms::performance::{PerformanceObservable, observe};
+use algorithms::performance::{PerformanceObservable, check_exponent, observe};
 use rand::{ThreadRng, Rng};
 
 struct VecWrap {
@@ -43,18 +43,12 @@ impl PerformanceObservable for DuplicateKeys {
 
 #[test]
 fn test_performance() {
-    let factor = observe::<VecWrap>(5000i64, None);
-    println!("Factor quick sort = {}", factor);
-
-    assert!(factor > 2.0);
-    assert!(factor < 3.5);
+    let report = observe::<VecWrap>(5000i64, None);
+    check_exponent("three_way_quick_sort", &report, 0.9..1.5);
 }
 
 #[test]
 fn test_performance_duplicate_keys() {
-    let factor = observe::<DuplicateKeys>(5000i64, None);
-    println!("Factor quick sort duplicate keys = {}", factor);
-
-    assert!(factor > 2.0);
-    assert!(factor < 3.5);
+    let report = observe::<DuplicateKeys>(5000i64, None);
+    check_exponent("three_way_quick_sort_duplicate_keys", &report, 0.7..1.5);
 }
\ No newline at end of file